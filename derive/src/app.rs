@@ -52,8 +52,39 @@ pub(crate) fn derive_register_state_helper(input: &DeriveInput, attrs: &StateAtt
             })
             .collect::<Punctuated<_, Token![,]>>();
 
+        let flush_in = attrs.flush_in.iter();
+
         let state_plugin_ty = concat(&crate_resolve_state_path, "ResolveStatePlugin");
-        quote! { #state_plugin_ty::<Self>::new(vec![#after], vec![#before]), }
+        quote! {
+            #state_plugin_ty::<Self>::new(vec![#after], vec![#before])
+                #(.flush_in(#flush_in))*,
+        }
+    };
+
+    // Construct `SubStatePlugin`.
+    let sub_state = if let Some(parent) = attrs.sub_of.as_ref() {
+        let crate_sub_state_path = concat(&crate_schedule_path, "sub_state");
+        let sub_state_plugin_ty = concat(&crate_sub_state_path, "SubStatePlugin");
+        let mut sub_state_plugin = if let Some(when) = attrs.when.as_ref() {
+            quote! { #sub_state_plugin_ty::<Self, #parent, _>::new(#when) }
+        } else {
+            quote! { #sub_state_plugin_ty::<Self, #parent>::default() }
+        };
+        if attrs.remember {
+            sub_state_plugin = quote! { #sub_state_plugin.remember() };
+        }
+        quote! { #sub_state_plugin, }
+    } else {
+        quote! {}
+    };
+
+    // Construct `ComputedStatePlugin`.
+    let computed_state = if attrs.computed {
+        let crate_computed_state_path = concat(&crate_schedule_path, "computed_state");
+        let computed_state_plugin_ty = concat(&crate_computed_state_path, "ComputedStatePlugin");
+        quote! { #computed_state_plugin_ty::<Self>::default(), }
+    } else {
+        quote! {}
     };
 
     // Construct simple plugins.
@@ -95,6 +126,15 @@ pub(crate) fn derive_register_state_helper(input: &DeriveInput, attrs: &StateAtt
             true,
         )
     };
+    let flush_observer = {
+        let crate_flush_observer_path = concat(&crate_schedule_path, "flush_observer");
+        plugin(
+            &crate_flush_observer_path,
+            "FlushObserver",
+            attrs.flush_observer,
+            true,
+        )
+    };
     #[cfg(not(feature = "debug"))]
     let log_flush = quote! {};
     #[cfg(feature = "debug")]
@@ -103,6 +143,19 @@ pub(crate) fn derive_register_state_helper(input: &DeriveInput, attrs: &StateAtt
         let crate_log_flush_path = concat(&crate_debug_path, "log_flush");
         plugin(&crate_log_flush_path, "LogFlush", attrs.log_flush, true)
     };
+    #[cfg(not(feature = "debug"))]
+    let flush_loop = quote! {};
+    #[cfg(feature = "debug")]
+    let flush_loop = {
+        let crate_debug_path = concat(&crate_path, "debug");
+        let crate_flush_loop_path = concat(&crate_debug_path, "flush_loop");
+        plugin(
+            &crate_flush_loop_path,
+            "FlushLoop",
+            attrs.detect_loop,
+            false,
+        )
+    };
     #[cfg(not(feature = "bevy_state"))]
     let bevy_state = quote! {};
     #[cfg(feature = "bevy_state")]
@@ -117,6 +170,52 @@ pub(crate) fn derive_register_state_helper(input: &DeriveInput, attrs: &StateAtt
         let crate_react_path = concat(&crate_extra_path, "react");
         plugin(&crate_react_path, "React", attrs.react, false)
     };
+    #[cfg(not(feature = "delay"))]
+    let delay = quote! {};
+    #[cfg(feature = "delay")]
+    let delay = {
+        let crate_delay_path = concat(&crate_extra_path, "delay");
+        plugin(&crate_delay_path, "Delay", attrs.delay, false)
+    };
+    #[cfg(not(feature = "history"))]
+    let history = quote! {};
+    #[cfg(feature = "history")]
+    let history = if let Some(capacity) = attrs.history.as_ref() {
+        let crate_history_path = concat(&crate_extra_path, "history");
+        let history_plugin_ty = concat(&crate_history_path, "HistoryPlugin");
+        quote! { #history_plugin_ty::<Self>::new(#capacity), }
+    } else {
+        quote! {}
+    };
+    #[cfg(not(feature = "requests"))]
+    let requests = quote! {};
+    #[cfg(feature = "requests")]
+    let requests = {
+        let crate_next_state_path = concat(&crate_path, "next_state");
+        let crate_requests_path = concat(&crate_next_state_path, "requests");
+        plugin(
+            &crate_requests_path,
+            "NextStateRequests",
+            attrs.requests,
+            true,
+        )
+    };
+    #[cfg(not(feature = "snapshot"))]
+    let snapshot = quote! {};
+    #[cfg(feature = "snapshot")]
+    let snapshot = {
+        let crate_snapshot_path = concat(&crate_extra_path, "snapshot");
+        plugin(&crate_snapshot_path, "Snapshot", attrs.snapshot, true)
+    };
+    let transition_table = {
+        let crate_transition_table_path = concat(&crate_schedule_path, "transition_table");
+        plugin(
+            &crate_transition_table_path,
+            "TransitionTable",
+            attrs.transitions.is_some(),
+            false,
+        )
+    };
     let apply_flush = {
         let crate_apply_flush_path = concat(&crate_schedule_path, "apply_flush");
         plugin(
@@ -127,18 +226,75 @@ pub(crate) fn derive_register_state_helper(input: &DeriveInput, attrs: &StateAtt
         )
     };
 
+    #[cfg(not(feature = "debug"))]
+    let state_info = quote! {};
+    #[cfg(feature = "debug")]
+    let state_info = {
+        let crate_debug_path = concat(&crate_path, "debug");
+        let crate_registry_path = concat(&crate_debug_path, "registry");
+        let state_registry_ty = concat(&crate_registry_path, "StateRegistry");
+        let state_info_ty = concat(&crate_registry_path, "StateInfo");
+
+        let new = if attrs.local {
+            quote! { new_local }
+        } else {
+            quote! { new }
+        };
+        let plugins = [
+            ("sub_state", attrs.sub_of.is_some()),
+            ("computed", attrs.computed),
+            ("transitions", attrs.transitions.is_some()),
+            ("detect_change", attrs.detect_change),
+            ("flush_event", attrs.flush_event),
+            ("flush_observer", attrs.flush_observer),
+            ("log_flush", attrs.log_flush),
+            ("detect_loop", attrs.detect_loop),
+            ("bevy_state", attrs.bevy_state),
+            ("react", attrs.react),
+            ("delay", attrs.delay),
+            ("history", attrs.history.is_some()),
+            ("requests", attrs.requests),
+            ("snapshot", attrs.snapshot),
+            ("apply_flush", attrs.apply_flush),
+        ]
+        .into_iter()
+        .filter_map(|(name, enable)| enable.then_some(name));
+
+        quote! {
+            #state_registry_ty::register(
+                app.world_mut(),
+                #state_info_ty::#new::<Self>().with_plugins([#(#plugins),*]),
+            );
+        }
+    };
+
     quote! {
         impl #impl_generics #register_state_trait for #ty_name #ty_generics #where_clause {
             fn register_state(app: &mut #app_ty) {
+                // Plugins are nested to stay within the tuple size limit of `Plugins`.
                 app.add_plugins((
-                    #resolve_state
-                    #detect_change
-                    #flush_event
-                    #log_flush
-                    #bevy_state
-                    #react
-                    #apply_flush
+                    (
+                        #resolve_state
+                        #sub_state
+                        #computed_state
+                        #transition_table
+                        #detect_change
+                        #flush_event
+                        #flush_observer
+                        #log_flush
+                    ),
+                    (
+                        #flush_loop
+                        #bevy_state
+                        #react
+                        #delay
+                        #history
+                        #requests
+                        #snapshot
+                        #apply_flush
+                    ),
                 ));
+                #state_info
             }
         }
     }
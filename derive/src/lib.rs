@@ -6,10 +6,11 @@ mod util;
 
 use bevy_macro_utils::BevyManifest;
 use proc_macro::TokenStream;
-use quote::quote;
+use proc_macro2::TokenTree;
+use quote::{ToTokens as _, quote};
 use syn::{
-    DeriveInput, Error, Meta, Path, Result, Token, Type, parse_macro_input, parse_str,
-    punctuated::Punctuated,
+    Data, DeriveInput, Error, Expr, Meta, Path, Result, Token, Type, parse_macro_input, parse_str,
+    parse2, punctuated::Punctuated,
 };
 
 use crate::util::concat;
@@ -18,7 +19,10 @@ use crate::util::concat;
 pub fn derive_state(input: TokenStream) -> TokenStream {
     // Parse the type and `#[state(...)]` attributes.
     let input = parse_macro_input!(input as DeriveInput);
-    let attrs = parse_state_attrs(&input).expect("Failed to parse state attributes");
+    let attrs = match parse_state_attrs(&input) {
+        Ok(attrs) => attrs,
+        Err(error) => return error.to_compile_error().into(),
+    };
 
     // Construct `State` impl.
     let impl_state = derive_state_helper(&input, &attrs);
@@ -29,11 +33,15 @@ pub fn derive_state(input: TokenStream) -> TokenStream {
     #[cfg(feature = "bevy_app")]
     let impl_register_state = app::derive_register_state_helper(&input, &attrs);
 
+    // Construct `TransitionTable` impl.
+    let impl_transition_table = derive_transition_table_helper(&input, &attrs);
+
     // Construct `Resource` impl.
     let impl_resource = derive_resource_helper(&input);
 
     quote! {
         #impl_state
+        #impl_transition_table
         #impl_register_state
         #impl_resource
     }
@@ -55,6 +63,138 @@ fn derive_resource_helper(input: &DeriveInput) -> proc_macro2::TokenStream {
     .into()
 }
 
+fn derive_transition_table_helper(
+    input: &DeriveInput,
+    attrs: &StateAttrs,
+) -> proc_macro2::TokenStream {
+    let Some(transitions) = attrs.transitions.as_ref() else {
+        return quote! {};
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let ty_name = &input.ident;
+
+    // Construct paths.
+    // TODO: This is not 100% portable I guess, but probably good enough.
+    let crate_path = parse_str::<Path>("pyri_state").unwrap();
+    let crate_schedule_path = concat(&crate_path, "schedule");
+    let crate_transition_table_path = concat(&crate_schedule_path, "transition_table");
+    let transition_table_trait = concat(&crate_transition_table_path, "TransitionTable");
+
+    // Construct the match expression.
+    let allows = if transitions.is_empty() {
+        quote! { false }
+    } else {
+        let transitions = transitions.iter().map(|transition| {
+            let old = &transition.old;
+            let new = &transition.new;
+            quote! { (#old, #new) }
+        });
+        quote! { ::core::matches!((old, new), #(#transitions)|*) }
+    };
+
+    // Construct the human-readable transitions.
+    let descriptions = transitions.iter().flat_map(|transition| {
+        transition.old_names.iter().flat_map(|old| {
+            transition
+                .new_names
+                .iter()
+                .map(move |new| quote! { (#old, #new) })
+        })
+    });
+
+    quote! {
+        impl #impl_generics #transition_table_trait for #ty_name #ty_generics #where_clause {
+            fn allows(old: &Self, new: &Self) -> bool {
+                #allows
+            }
+
+            fn transitions() -> &'static [(&'static str, &'static str)] {
+                &[#(#descriptions),*]
+            }
+        }
+    }
+}
+
+// A parsed `Old => New` transition.
+struct Transition {
+    // The old state pattern.
+    old: proc_macro2::TokenStream,
+    // The new state pattern.
+    new: proc_macro2::TokenStream,
+    // The human-readable alternatives of the old state pattern.
+    old_names: Vec<String>,
+    // The human-readable alternatives of the new state pattern.
+    new_names: Vec<String>,
+}
+
+// Parse `Old => New, ...` transitions, where each side is a pattern. For an enum, bare variant
+// names have an implicit `Self::` prefix (e.g. `Title | Gameplay(_)`).
+fn parse_transitions(tokens: proc_macro2::TokenStream, is_enum: bool) -> Result<Vec<Transition>> {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let is_punct =
+        |token: &TokenTree, c: char| matches!(token, TokenTree::Punct(p) if p.as_char() == c);
+
+    // Prefix each alternative with `Self::` if it's a bare variant name.
+    let parse_side = |side: &[TokenTree]| -> Result<_> {
+        let mut alternatives = vec![];
+        let mut names = vec![];
+        for alt in side.split(|token| is_punct(token, '|')) {
+            let alt_tokens = alt.iter().cloned().collect::<proc_macro2::TokenStream>();
+            let is_bare = matches!(
+                alt.first(),
+                Some(TokenTree::Ident(ident)) if ident != "Self" && ident != "_",
+            ) && !matches!(alt.get(1), Some(token) if is_punct(token, ':'));
+            names.push(alt_tokens.to_string());
+            alternatives.push(match (is_bare, is_enum) {
+                (true, true) => quote! { Self::#alt_tokens },
+                (true, false) => {
+                    return Err(Error::new_spanned(
+                        alt_tokens,
+                        "expected a `Self(..)` or `Self { .. }` pattern for a struct state",
+                    ));
+                }
+                (false, _) => alt_tokens,
+            });
+        }
+        Ok((quote! { #(#alternatives)|* }, names))
+    };
+
+    let mut transitions = vec![];
+    for entry in tokens.split(|token| is_punct(token, ',')) {
+        if entry.is_empty() {
+            continue;
+        }
+
+        let invalid = || {
+            Error::new_spanned(
+                entry.iter().cloned().collect::<proc_macro2::TokenStream>(),
+                "expected `Old => New` transition",
+            )
+        };
+        let Some(arrow) = entry
+            .windows(2)
+            .position(|x| is_punct(&x[0], '=') && is_punct(&x[1], '>'))
+        else {
+            return Err(invalid());
+        };
+        let (old, new) = (&entry[..arrow], &entry[arrow + 2..]);
+        if old.is_empty() || new.is_empty() {
+            return Err(invalid());
+        }
+
+        let (old, old_names) = parse_side(old)?;
+        let (new, new_names) = parse_side(new)?;
+        transitions.push(Transition {
+            old,
+            new,
+            old_names,
+            new_names,
+        });
+    }
+
+    Ok(transitions)
+}
+
 fn derive_state_helper(input: &DeriveInput, attrs: &StateAttrs) -> proc_macro2::TokenStream {
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     let ty_name = &input.ident;
@@ -70,6 +210,14 @@ fn derive_state_helper(input: &DeriveInput, attrs: &StateAttrs) -> proc_macro2::
         quote! {
             #next
         }
+    } else if attrs.requests {
+        let crate_next_state_path = concat(&crate_path, "next_state");
+        let crate_requests_path = concat(&crate_next_state_path, "requests");
+        let state_requests_ty = concat(&crate_requests_path, "NextStateRequests");
+
+        quote! {
+            #state_requests_ty<Self>
+        }
     } else {
         let crate_next_state_path = concat(&crate_path, "next_state");
         let crate_buffer_path = concat(&crate_next_state_path, "buffer");
@@ -80,10 +228,21 @@ fn derive_state_helper(input: &DeriveInput, attrs: &StateAttrs) -> proc_macro2::
         }
     };
 
+    // Construct `HIERARCHICAL` const.
+    let hierarchical = if attrs.hierarchical {
+        quote! {
+            const HIERARCHICAL: bool = true;
+        }
+    } else {
+        quote! {}
+    };
+
     // Construct `State` impl.
     quote! {
         impl #impl_generics #state_trait for #ty_name #ty_generics #where_clause {
             type Next = #next_ty;
+
+            #hierarchical
         }
     }
     .into()
@@ -95,12 +254,25 @@ struct StateAttrs {
     local: bool,
     after: Punctuated<Type, Token![,]>,
     before: Punctuated<Type, Token![,]>,
+    flush_in: Punctuated<Expr, Token![,]>,
+    sub_of: Option<Type>,
+    when: Option<Expr>,
+    remember: bool,
+    computed: bool,
     no_defaults: bool,
     detect_change: bool,
     flush_event: bool,
+    flush_observer: bool,
     log_flush: bool,
+    detect_loop: bool,
     bevy_state: bool,
     react: bool,
+    delay: bool,
+    history: Option<Expr>,
+    requests: bool,
+    snapshot: bool,
+    hierarchical: bool,
+    transitions: Option<Vec<Transition>>,
     apply_flush: bool,
 }
 
@@ -128,10 +300,34 @@ fn parse_state_attrs(input: &DeriveInput) -> Result<StateAttrs> {
                         .expect("invalid `before` states");
                 }
 
+                Meta::List(meta) if meta.path.is_ident("flush_in") => {
+                    state_attrs.flush_in = meta
+                        .parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+                        .expect("invalid `flush_in` schedules");
+                }
+
                 Meta::List(meta) if meta.path.is_ident("next") => {
                     state_attrs.next = Some(meta.parse_args().expect("invalid `next` type"));
                 }
 
+                Meta::List(meta) if meta.path.is_ident("transitions") => {
+                    let is_enum = matches!(input.data, Data::Enum(_));
+                    state_attrs.transitions = Some(parse_transitions(meta.tokens, is_enum)?);
+                }
+
+                Meta::NameValue(meta) if meta.path.is_ident("sub_of") => {
+                    state_attrs.sub_of =
+                        Some(parse2(meta.value.to_token_stream()).expect("invalid `sub_of` state"));
+                }
+
+                Meta::NameValue(meta) if meta.path.is_ident("when") => {
+                    state_attrs.when = Some(meta.value);
+                }
+
+                Meta::NameValue(meta) if meta.path.is_ident("history") => {
+                    state_attrs.history = Some(meta.value);
+                }
+
                 Meta::Path(path) => {
                     let Some(ident) = path.get_ident() else {
                         return Err(Error::new_spanned(path, "invalid state attribute"));
@@ -142,10 +338,18 @@ fn parse_state_attrs(input: &DeriveInput) -> Result<StateAttrs> {
                         "local" => state_attrs.local = true,
                         "detect_change" => state_attrs.detect_change = true,
                         "flush_event" => state_attrs.flush_event = true,
+                        "flush_observer" => state_attrs.flush_observer = true,
                         "log_flush" => state_attrs.log_flush = true,
+                        "detect_loop" => state_attrs.detect_loop = true,
                         "bevy_state" => state_attrs.bevy_state = true,
                         "react" => state_attrs.react = true,
+                        "delay" => state_attrs.delay = true,
+                        "requests" => state_attrs.requests = true,
+                        "snapshot" => state_attrs.snapshot = true,
+                        "hierarchical" => state_attrs.hierarchical = true,
                         "apply_flush" => state_attrs.apply_flush = true,
+                        "remember" => state_attrs.remember = true,
+                        "computed" => state_attrs.computed = true,
                         _ => return Err(Error::new_spanned(ident, "invalid state attribute")),
                     }
                 }
@@ -155,6 +359,20 @@ fn parse_state_attrs(input: &DeriveInput) -> Result<StateAttrs> {
         }
     }
 
+    if state_attrs.sub_of.is_none() && (state_attrs.when.is_some() || state_attrs.remember) {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`when` and `remember` require `sub_of`",
+        ));
+    }
+
+    if state_attrs.sub_of.is_some() && state_attrs.computed {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`sub_of` and `computed` can't be used together",
+        ));
+    }
+
     // Enable default options.
     if !state_attrs.no_defaults {
         state_attrs.detect_change = true;
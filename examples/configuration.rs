@@ -3,7 +3,9 @@
 use bevy::prelude::*;
 use pyri_state::{
     debug::log_flush::LogFlushPlugin,
-    extra::{bevy_state::BevyStatePlugin, react::ReactPlugin},
+    extra::{
+        bevy_state::BevyStatePlugin, delay::DelayPlugin, history::HistoryPlugin, react::ReactPlugin,
+    },
     prelude::*,
     schedule::{
         apply_flush::ApplyFlushPlugin, detect_change::DetectChangePlugin,
@@ -18,6 +20,7 @@ fn main() {
         .add_state::<BasicState>()
         .add_state::<RawState>()
         .add_state::<CustomState>()
+        .add_state::<ComputedFlag>()
         .run();
 }
 
@@ -31,7 +34,7 @@ struct BasicState;
 struct RawState;
 
 // The built-in state plugins can be configured:
-#[derive(State, Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(State, Clone, PartialEq, Eq, Hash, Debug, Default)]
 #[state(
     // Disable default plugins: detect_change, flush_event, apply_flush.
     no_defaults,
@@ -46,6 +49,10 @@ struct RawState;
     bevy_state,
     // Enable reaction components such as `DespawnOnExitState<Self>` (requires Eq).
     react,
+    // Enable delayed flushes with `DelayedStateFlush<Self>` (requires StateMut).
+    delay,
+    // Record the last N flushed values in `StateHistory<Self>` (requires Clone, PartialEq).
+    history = 10,
     // Clone the next state into the current state on flush (requires Clone).
     apply_flush,
     // Swap out the default `NextStateBuffer<Self>` for another `NextState` type.
@@ -55,9 +62,31 @@ struct RawState;
     after(BasicState, RawState),
     // Run this state's on-flush hooks before the listed states.
     before(CustomState),
+    // Enable this state while a parent state matches a pattern (requires StateMut, Clone, Default).
+    sub_of = BasicState,
+    // The pattern for `sub_of` (defaults to `BasicState::ANY`).
+    when = BasicState,
+    // Re-enable this state with its last value instead of its default value.
+    remember,
 )]
 struct DerivedState;
 
+// A state can be computed from its sources instead (can't be combined with `sub_of`):
+#[derive(State, Clone, PartialEq, Eq)]
+#[state(
+    // Compute the next state from its sources (requires ComputedState).
+    computed,
+)]
+struct ComputedFlag;
+
+impl ComputedState for ComputedFlag {
+    type Sources = (BasicState, RawState);
+
+    fn compute((basic, raw): (Option<&BasicState>, Option<&RawState>)) -> Option<Self> {
+        (basic.is_some() && raw.is_some()).then_some(ComputedFlag)
+    }
+}
+
 // Skip the derive entirely to fully customize your state type (see below).
 #[derive(Resource, Clone, PartialEq, Eq, Hash, Debug)]
 struct CustomState;
@@ -80,6 +109,8 @@ impl RegisterState for CustomState {
             LogFlushPlugin::<Self>::default(),
             BevyStatePlugin::<Self>::default(),
             ReactPlugin::<Self>::default(),
+            DelayPlugin::<Self>::default(),
+            HistoryPlugin::<Self>::new(10),
             ApplyFlushPlugin::<Self>::default(),
         ));
 
@@ -9,18 +9,6 @@ fn main() {
         .init_state::<Screen>()
         .add_state::<CheckerboardSquare>()
         .add_state::<SquareColor>()
-        .add_systems(
-            StateFlush,
-            (
-                // Enable `CheckerboardSquare` only during `Screen::Gameplay`.
-                Screen::Gameplay.on_edge(
-                    CheckerboardSquare::disable,
-                    CheckerboardSquare::enable_default,
-                ),
-                // Compute `SquareColor` from `CheckerboardSquare`.
-                CheckerboardSquare::ANY.on_enter(compute_square_color),
-            ),
-        )
         .run();
 }
 
@@ -33,7 +21,7 @@ enum Screen {
 
 // Substate of `Screen::Gameplay`
 #[derive(State, Clone, PartialEq, Eq, Default)]
-#[state(after(Screen))]
+#[state(sub_of = Screen, when = Screen::Gameplay)]
 struct CheckerboardSquare {
     row: u8,
     col: u8,
@@ -41,18 +29,22 @@ struct CheckerboardSquare {
 
 // Computed from `CheckerboardSquare`
 #[derive(State, Clone, PartialEq, Eq)]
-#[state(after(CheckerboardSquare))]
+#[state(computed)]
 enum SquareColor {
     Black,
     White,
 }
 
-fn compute_square_color(board: NextRef<CheckerboardSquare>, mut color: NextMut<SquareColor>) {
-    color.set(board.get().map(|board| {
-        if board.row + board.col % 2 == 0 {
-            SquareColor::Black
-        } else {
-            SquareColor::White
-        }
-    }));
+impl ComputedState for SquareColor {
+    type Sources = CheckerboardSquare;
+
+    fn compute(board: Option<&CheckerboardSquare>) -> Option<Self> {
+        board.map(|board| {
+            if board.row + board.col % 2 == 0 {
+                SquareColor::Black
+            } else {
+                SquareColor::White
+            }
+        })
+    }
 }
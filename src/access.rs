@@ -10,13 +10,19 @@
 //! | Current & Next | [`FlushRef<S>`]   | [`FlushMut<S>`]    |
 //!
 //! \* NOTE: Don't mutate the current state directly unless you know what you're doing.
+//!
+//! For [`LocalState`] types, use [`LocalFlushRef<S>`] to access the current and next states
+//! of every entity at once.
 
-use bevy_ecs::system::{Res, ResMut, StaticSystemParam, SystemParam};
+use bevy_ecs::{
+    entity::Entity,
+    system::{Query, Res, ResMut, StaticSystemParam, SystemParam},
+};
 
 use crate::{
     next_state::{NextState, NextStateMut, TriggerStateFlush},
     pattern::{StatePattern, StateTransPattern},
-    state::{State, StateMut},
+    state::{LocalState, State, StateMut},
 };
 
 // TODO: Manually impl `SystemParam` to skip the query and contain `Option<&S>` directly (if that's possible).
@@ -358,8 +364,12 @@ impl<S: State> FlushRef<'_, '_, S> {
     }
 
     /// Check if `S` will exit a state that matches a specific pattern if triggered.
+    ///
+    /// If `S` is [hierarchical](State::HIERARCHICAL), this is false when the next state also
+    /// matches the pattern (except for [`State::ANY`]).
     pub fn will_exit<P: StatePattern<S>>(&self, pattern: &P) -> bool {
-        matches!(self.get(), (Some(x), _) if pattern.matches(x))
+        let (x, y) = self.get();
+        will_exit(x, y, pattern)
     }
 
     /// Check if `S` will become disabled from a state that matches a specific pattern if triggered.
@@ -368,8 +378,12 @@ impl<S: State> FlushRef<'_, '_, S> {
     }
 
     /// Check if `S` will enter a state that matches a specific pattern if triggered.
+    ///
+    /// If `S` is [hierarchical](State::HIERARCHICAL), this is false when the current state
+    /// also matches the pattern (except for [`State::ANY`]).
     pub fn will_enter<P: StatePattern<S>>(&self, pattern: &P) -> bool {
-        matches!(self.get(), (_, Some(y)) if pattern.matches(y))
+        let (x, y) = self.get();
+        will_enter(x, y, pattern)
     }
 
     /// Check if `S` will become enabled in a state that matches a specific pattern if triggered.
@@ -458,8 +472,12 @@ impl<S: StateMut> FlushMut<'_, '_, S> {
     }
 
     /// Check if `S` will exit a state that matches a specific pattern if triggered.
+    ///
+    /// If `S` is [hierarchical](State::HIERARCHICAL), this is false when the next state also
+    /// matches the pattern (except for [`State::ANY`]).
     pub fn will_exit<P: StatePattern<S>>(&self, pattern: &P) -> bool {
-        matches!(self.get(), (Some(x), _) if pattern.matches(x))
+        let (x, y) = self.get();
+        will_exit(x, y, pattern)
     }
 
     /// Check if `S` will become disabled from a state that matches a specific pattern if triggered.
@@ -468,8 +486,12 @@ impl<S: StateMut> FlushMut<'_, '_, S> {
     }
 
     /// Check if `S` will enter a state that matches a specific pattern if triggered.
+    ///
+    /// If `S` is [hierarchical](State::HIERARCHICAL), this is false when the current state
+    /// also matches the pattern (except for [`State::ANY`]).
     pub fn will_enter<P: StatePattern<S>>(&self, pattern: &P) -> bool {
-        matches!(self.get(), (_, Some(y)) if pattern.matches(y))
+        let (x, y) = self.get();
+        will_enter(x, y, pattern)
     }
 
     /// Check if `S` will become enabled in a state that matches a specific pattern if triggered.
@@ -545,3 +567,152 @@ impl<S: StateMut + Default> FlushMut<'_, '_, S> {
         self.next.enter_default();
     }
 }
+
+/// A [`SystemParam`] with read-only access to the current and next values of the [`LocalState`]
+/// type `S` on every entity.
+///
+/// NOTE: The next state is only set in stone during the [`StateFlush`](crate::schedule::StateFlush)
+/// schedule after [`ResolveStateSet::<S>::Compute`](crate::schedule::ResolveStateSet::Compute).
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use pyri_state::prelude::*;
+/// #
+/// # #[derive(State, Component, Clone, PartialEq, Eq)]
+/// # #[state(local)]
+/// # enum EnemyAi {
+/// #     Idle,
+/// #     Chase,
+/// # }
+/// #
+/// fn alert_chasing_enemies(ai: LocalFlushRef<EnemyAi>) {
+///     for entity in ai.iter_enter(&EnemyAi::Chase) {
+///         info!("{entity} started chasing!");
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct LocalFlushRef<'w, 's, S: LocalState> {
+    next_param: StaticSystemParam<'w, 's, <<S as State>::Next as NextState>::Param>,
+    state_query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            Option<&'static S>,
+            &'static <S as State>::Next,
+            &'static TriggerStateFlush<S>,
+        ),
+    >,
+}
+
+impl<S: LocalState> LocalFlushRef<'_, '_, S> {
+    /// Get read-only references to the current and next states of an entity, or `None` if
+    /// disabled.
+    ///
+    /// Returns `None` if the entity does not have `S` as a local state.
+    pub fn get(&self, entity: Entity) -> Option<(Option<&S>, Option<&S>)> {
+        let (_, current, next, _) = self.state_query.get(entity).ok()?;
+        Some((current, next.next_state(&self.next_param)))
+    }
+
+    /// Check if `S` is triggered to flush for an entity in the
+    /// [`StateFlush`](crate::schedule::StateFlush) schedule.
+    pub fn is_triggered(&self, entity: Entity) -> bool {
+        self.state_query
+            .get(entity)
+            .is_ok_and(|(_, _, _, trigger)| trigger.0)
+    }
+
+    /// Iterate over the current and next states of every entity with `S` as a local state.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Option<&S>, Option<&S>)> {
+        self.state_query
+            .iter()
+            .map(|(entity, current, next, _)| (entity, current, next.next_state(&self.next_param)))
+    }
+
+    /// Iterate over the current and next states of every entity that is triggered to flush `S`.
+    pub fn iter_triggered(&self) -> impl Iterator<Item = (Entity, Option<&S>, Option<&S>)> {
+        self.state_query
+            .iter()
+            .filter(|(_, _, _, trigger)| trigger.0)
+            .map(|(entity, current, next, _)| (entity, current, next.next_state(&self.next_param)))
+    }
+
+    /// Iterate over every entity whose current state is enabled and matches a specific pattern.
+    pub fn iter_in<'a, P: StatePattern<S>>(
+        &'a self,
+        pattern: &'a P,
+    ) -> impl 'a + Iterator<Item = Entity> {
+        self.iter().filter_map(move |(entity, x, _)| {
+            matches!(x, Some(x) if pattern.matches(x)).then_some(entity)
+        })
+    }
+
+    /// Iterate over every entity that will exit a state that matches a specific pattern if
+    /// triggered.
+    pub fn iter_exit<'a, P: StatePattern<S>>(
+        &'a self,
+        pattern: &'a P,
+    ) -> impl 'a + Iterator<Item = Entity> {
+        self.iter_triggered()
+            .filter_map(move |(entity, x, y)| will_exit(x, y, pattern).then_some(entity))
+    }
+
+    /// Iterate over every entity that will become disabled from a state that matches a specific
+    /// pattern if triggered.
+    pub fn iter_disable<'a, P: StatePattern<S>>(
+        &'a self,
+        pattern: &'a P,
+    ) -> impl 'a + Iterator<Item = Entity> {
+        self.iter_triggered().filter_map(move |(entity, x, y)| {
+            matches!((x, y), (Some(x), None) if pattern.matches(x)).then_some(entity)
+        })
+    }
+
+    /// Iterate over every entity that will enter a state that matches a specific pattern if
+    /// triggered.
+    pub fn iter_enter<'a, P: StatePattern<S>>(
+        &'a self,
+        pattern: &'a P,
+    ) -> impl 'a + Iterator<Item = Entity> {
+        self.iter_triggered()
+            .filter_map(move |(entity, x, y)| will_enter(x, y, pattern).then_some(entity))
+    }
+
+    /// Iterate over every entity that will become enabled in a state that matches a specific
+    /// pattern if triggered.
+    pub fn iter_enable<'a, P: StatePattern<S>>(
+        &'a self,
+        pattern: &'a P,
+    ) -> impl 'a + Iterator<Item = Entity> {
+        self.iter_triggered().filter_map(move |(entity, x, y)| {
+            matches!((x, y), (None, Some(y)) if pattern.matches(y)).then_some(entity)
+        })
+    }
+
+    /// Iterate over every entity that will undergo a transition that matches a specific pattern
+    /// if triggered.
+    pub fn iter_trans<'a, P: StateTransPattern<S>>(
+        &'a self,
+        pattern: &'a P,
+    ) -> impl 'a + Iterator<Item = Entity> {
+        self.iter_triggered().filter_map(move |(entity, x, y)| {
+            matches!((x, y), (Some(x), Some(y)) if pattern.matches(x, y)).then_some(entity)
+        })
+    }
+}
+
+/// Check if a flush from `x` to `y` will exit a state that matches a specific pattern.
+fn will_exit<S: State, P: StatePattern<S>>(x: Option<&S>, y: Option<&S>, pattern: &P) -> bool {
+    matches!(x, Some(x) if pattern.matches(x))
+        && !(S::HIERARCHICAL && P::HIERARCHICAL && matches!(y, Some(y) if pattern.matches(y)))
+}
+
+/// Check if a flush from `x` to `y` will enter a state that matches a specific pattern.
+fn will_enter<S: State, P: StatePattern<S>>(x: Option<&S>, y: Option<&S>, pattern: &P) -> bool {
+    matches!(y, Some(y) if pattern.matches(y))
+        && !(S::HIERARCHICAL && P::HIERARCHICAL && matches!(x, Some(x) if pattern.matches(x)))
+}
@@ -4,11 +4,21 @@
 //!
 //! Insert the [`StateDebugSettings`] resource to enable debug tools.
 
+pub mod diagram;
+pub mod flush_history;
+pub mod flush_loop;
 pub mod log_flush;
+pub mod registry;
+
+use alloc::{vec, vec::Vec};
 
 #[cfg(feature = "bevy_reflect")]
 use bevy_ecs::reflect::ReflectResource;
-use bevy_ecs::resource::Resource;
+use bevy_ecs::{
+    resource::Resource,
+    schedule::{NodeId, Schedule, SystemSet, graph::Direction::Outgoing},
+    system::ScheduleSystem,
+};
 
 /// A resource that controls the behavior of [state debugging tools](crate::debug).
 #[derive(Resource, PartialEq, Eq, Default)]
@@ -26,6 +36,54 @@ pub struct StateDebugSettings {
     pub log_trans: bool,
     /// Enable on-enter logs.
     pub log_enter: bool,
+    /// Enable on-reject logs.
+    pub log_reject: bool,
     /// Enable logging for local states.
     pub log_local: bool,
+    /// Enable recording flushes in the [`StateFlushHistory`](flush_history::StateFlushHistory).
+    pub record_flush: bool,
+    /// Enable recording flushes of local states in the
+    /// [`StateFlushHistory`](flush_history::StateFlushHistory).
+    pub record_local: bool,
+    /// The number of consecutive frames a state must flush in a repeating cycle to be detected
+    /// as a [flush loop](flush_loop), or 0 to disable flush loop detection.
+    pub loop_frames: u32,
+    /// The maximum length of a repeating cycle to detect as a [flush loop](flush_loop), or 0 for
+    /// half of [`Self::loop_frames`].
+    pub loop_period: u32,
+}
+
+/// Get the systems in a system set, including nested system sets.
+pub(crate) fn systems_in_set<'a>(
+    schedule: &'a Schedule,
+    set: &dyn SystemSet,
+) -> Vec<&'a ScheduleSystem> {
+    let graph = schedule.graph();
+    let Some((set_id, _, _)) = graph.system_sets().find(|(_, x, _)| *x == set) else {
+        return vec![];
+    };
+
+    // Collect the system node IDs in the set.
+    let mut system_ids = vec![];
+    let mut stack = vec![set_id];
+    while let Some(id) = stack.pop() {
+        for child in graph.hierarchy().graph().neighbors_directed(id, Outgoing) {
+            match child {
+                NodeId::System(_) => system_ids.push(child),
+                NodeId::Set(_) => stack.push(child),
+            }
+        }
+    }
+
+    // Systems are moved out of the schedule graph once the schedule is initialized.
+    match schedule.systems() {
+        Ok(systems) => systems
+            .filter(|(id, _)| system_ids.contains(id))
+            .map(|(_, system)| system)
+            .collect(),
+        Err(_) => system_ids
+            .into_iter()
+            .filter_map(|id| graph.get_system_at(id))
+            .collect(),
+    }
 }
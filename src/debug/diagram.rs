@@ -0,0 +1,170 @@
+//! Export a [`StateDiagram`] as a Graphviz DOT or Mermaid graph.
+//!
+//! # Example
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use pyri_state::{debug::diagram::StateDiagram, prelude::*};
+//! #
+//! #[derive(State, Clone, PartialEq, Eq)]
+//! #[state(transitions(Title => Gameplay, Gameplay => Title))]
+//! enum Screen {
+//!     Title,
+//!     Gameplay,
+//! }
+//!
+//! # fn spawn_level() {}
+//! #
+//! let mut app = App::new();
+//! app.add_plugins(StatePlugin).add_state::<Screen>();
+//! app.add_systems(StateFlush, Screen::Gameplay.on_enter(spawn_level));
+//!
+//! let dot = StateDiagram::from_app::<Screen>(&app)
+//!     .with_transitions(Screen::transitions())
+//!     .to_dot();
+//! assert!(dot.contains(r#""Title" -> "Gameplay";"#));
+//! assert!(dot.contains("spawn_level"));
+//! ```
+
+use alloc::{
+    borrow::ToOwned as _,
+    format,
+    string::{String, ToString as _},
+    vec,
+    vec::Vec,
+};
+use core::{any::type_name, fmt::Write as _};
+
+#[cfg(feature = "bevy_app")]
+use bevy_app::App;
+use bevy_ecs::schedule::{Schedule, SystemSet};
+
+#[cfg(feature = "bevy_app")]
+use crate::schedule::StateFlush;
+use crate::{debug::systems_in_set, schedule::ResolveStateSet, state::State};
+
+/// A diagram of the [`State`] type `S`, built from its transition table and the hook systems
+/// in its [`ResolveStateSet::Exit`], [`ResolveStateSet::Trans`], and
+/// [`ResolveStateSet::Enter`] system sets.
+///
+/// Hook run conditions can't be inspected, so hook systems are listed per system set rather than
+/// attached to specific transitions.
+#[derive(Clone, Debug)]
+pub struct StateDiagram {
+    name: String,
+    transitions: Vec<(String, String)>,
+    hooks: Vec<(&'static str, Vec<String>)>,
+}
+
+impl StateDiagram {
+    /// Build a `StateDiagram` for the [`State`] type `S` from the hook systems in a
+    /// [`StateFlush`](crate::schedule::StateFlush) schedule.
+    pub fn new<S: State>(schedule: &Schedule) -> Self {
+        Self {
+            name: type_name::<S>().to_owned(),
+            transitions: vec![],
+            hooks: vec![
+                ("Exit", hook_names(schedule, &ResolveStateSet::<S>::Exit)),
+                ("Trans", hook_names(schedule, &ResolveStateSet::<S>::Trans)),
+                ("Enter", hook_names(schedule, &ResolveStateSet::<S>::Enter)),
+            ],
+        }
+    }
+
+    /// Build a `StateDiagram` for the [`State`] type `S` from the hook systems in the
+    /// [`StateFlush`] schedule of an [`App`].
+    #[cfg(feature = "bevy_app")]
+    pub fn from_app<S: State>(app: &App) -> Self {
+        Self::new::<S>(app.get_schedule(StateFlush).unwrap())
+    }
+
+    /// Add transitions as `(old, new)` pairs, such as from
+    /// [`TransitionTable::transitions`](crate::schedule::transition_table::TransitionTable::transitions).
+    pub fn with_transitions<'a>(
+        mut self,
+        transitions: impl IntoIterator<Item = &'a (&'a str, &'a str)>,
+    ) -> Self {
+        self.transitions.extend(
+            transitions
+                .into_iter()
+                .map(|(old, new)| (old.to_string(), new.to_string())),
+        );
+        self
+    }
+
+    /// Render the diagram as a Graphviz DOT graph.
+    pub fn to_dot(&self) -> String {
+        let escape = |x: &str| x.replace('\\', "\\\\").replace('"', "\\\"");
+
+        let mut dot = format!("digraph \"{}\" {{\n", escape(&self.name));
+        for node in self.nodes() {
+            let _ = writeln!(dot, "    \"{}\";", escape(node));
+        }
+        for (old, new) in &self.transitions {
+            let _ = writeln!(dot, "    \"{}\" -> \"{}\";", escape(old), escape(new));
+        }
+        if let Some(label) = self.hooks_label("\\l", &escape) {
+            let _ = writeln!(dot, "    \"hooks\" [shape=note, label=\"{label}\\l\"];");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Render the diagram as a Mermaid flowchart.
+    pub fn to_mermaid(&self) -> String {
+        let escape = |x: &str| x.replace('"', "#quot;");
+
+        let nodes = self.nodes();
+        let id = |x: &str| nodes.iter().position(|node| *node == x).unwrap();
+
+        let mut mermaid = format!(
+            "---\ntitle: \"{}\"\n---\nflowchart LR\n",
+            escape(&self.name)
+        );
+        for (i, node) in nodes.iter().enumerate() {
+            let _ = writeln!(mermaid, "    s{i}[\"{}\"]", escape(node));
+        }
+        for (old, new) in &self.transitions {
+            let _ = writeln!(mermaid, "    s{} --> s{}", id(old), id(new));
+        }
+        if let Some(label) = self.hooks_label("<br>", &escape) {
+            let _ = writeln!(mermaid, "    hooks>\"{label}\"]");
+        }
+        mermaid
+    }
+
+    /// The unique states in the transitions, in order of first appearance.
+    fn nodes(&self) -> Vec<&str> {
+        let mut nodes = Vec::<&str>::new();
+        for (old, new) in &self.transitions {
+            for node in [old, new] {
+                if !nodes.contains(&node.as_str()) {
+                    nodes.push(node);
+                }
+            }
+        }
+        nodes
+    }
+
+    /// A label listing the hook systems in each system set, or `None` if there are no hooks.
+    fn hooks_label(&self, newline: &str, escape: &dyn Fn(&str) -> String) -> Option<String> {
+        let lines = self
+            .hooks
+            .iter()
+            .flat_map(|(set, names)| names.iter().map(move |name| format!("{set}: {name}")))
+            .map(|line| escape(&line))
+            .collect::<Vec<_>>();
+        (!lines.is_empty()).then(|| lines.join(newline))
+    }
+}
+
+/// Get the sorted names of the systems in a system set, including nested system sets.
+fn hook_names(schedule: &Schedule, set: &dyn SystemSet) -> Vec<String> {
+    let mut names = systems_in_set(schedule, set)
+        .into_iter()
+        .map(|system| system.name().into_owned())
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names
+}
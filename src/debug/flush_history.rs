@@ -0,0 +1,431 @@
+//! Record every state flush in a [`StateFlushHistory`].
+//!
+//! Add the [`StateFlushHistoryPlugin`] and enable
+//! [`StateDebugSettings::record_flush`](crate::debug::StateDebugSettings::record_flush) to
+//! record flushes for every state type added with [`AppExtState`](crate::setup::AppExtState).
+//! Local state flushes are only recorded if
+//! [`record_local`](crate::debug::StateDebugSettings::record_local) is also enabled.
+//!
+//! State values are recorded through reflection, so the state type must be registered in the
+//! [`AppTypeRegistry`](bevy_ecs::reflect::AppTypeRegistry) with
+//! [`ReflectResource`](bevy_ecs::reflect::ReflectResource) (or
+//! [`ReflectComponent`](bevy_ecs::reflect::ReflectComponent) for local states). Otherwise,
+//! enabled values are recorded as `"enabled"`.
+//!
+//! # Example
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use pyri_state::{
+//! #     debug::{StateDebugSettings, flush_history::{StateFlushHistory, StateFlushHistoryPlugin}},
+//! #     prelude::*,
+//! # };
+//! #
+//! #[derive(State, Reflect, Clone, PartialEq, Eq)]
+//! #[reflect(Resource)]
+//! enum Screen {
+//!     Title,
+//!     Gameplay,
+//! }
+//!
+//! # fn spawn_level() {}
+//! #
+//! let mut app = App::new();
+//! app.add_plugins((MinimalPlugins, StatePlugin, StateFlushHistoryPlugin))
+//!     .insert_resource(StateDebugSettings {
+//!         record_flush: true,
+//!         ..default()
+//!     })
+//!     .add_state::<Screen>()
+//!     .register_type::<Screen>()
+//!     .add_systems(StateFlush, Screen::Gameplay.on_enter(spawn_level));
+//!
+//! app.world_mut().resource_mut::<NextStateBuffer<Screen>>().enter(Screen::Gameplay);
+//! app.update();
+//!
+//! let history = app.world().resource::<StateFlushHistory>();
+//! let record = history.iter_state::<Screen>().last().unwrap();
+//! assert_eq!(record.new.as_deref(), Some("Gameplay"));
+//! assert!(record.enter.iter().any(|x| x.contains("spawn_level")));
+//!
+//! // Dump the history to a file.
+//! # let _ = |path: &str| -> std::io::Result<()> {
+//! std::fs::write(path, history.to_string())?;
+//! # Ok(()) };
+//! ```
+
+#[cfg(feature = "bevy_app")]
+pub use app::*;
+
+#[cfg(feature = "bevy_app")]
+mod app {
+    use alloc::vec::Vec;
+
+    use bevy_app::{App, Last, Plugin};
+
+    use crate::{debug::registry::StateRegistry, schedule::StateFlush};
+
+    use super::{StateFlushHistory, update_state_flush_history};
+
+    /// A plugin that adds the [`StateFlushHistory`] resource and systems to record each flush of
+    /// every state type added with [`AppExtState`](crate::setup::AppExtState), before or after
+    /// this plugin.
+    pub struct StateFlushHistoryPlugin;
+
+    impl Plugin for StateFlushHistoryPlugin {
+        fn build(&self, app: &mut App) {
+            app.init_resource::<StateFlushHistory>()
+                .add_systems(Last, update_state_flush_history);
+
+            // States added after this plugin are handled by `AppExtState`.
+            let states = app
+                .world()
+                .get_resource::<StateRegistry>()
+                .map(|x| x.iter().cloned().collect::<Vec<_>>())
+                .unwrap_or_default();
+            let schedule = app.get_schedule_mut(StateFlush).unwrap();
+            for info in states {
+                info.schedule_record_flush(schedule);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+use alloc::format;
+use alloc::{
+    collections::VecDeque,
+    string::{String, ToString as _},
+    vec,
+    vec::Vec,
+};
+use core::{
+    any::{TypeId, type_name},
+    fmt::{self, Display},
+    time::Duration,
+};
+
+use bevy_diagnostic::FrameCount;
+use bevy_ecs::{
+    change_detection::Mut,
+    component::Tick,
+    entity::Entity,
+    intern::Interned,
+    resource::Resource,
+    schedule::{Condition as _, IntoScheduleConfigs as _, Schedule, Schedules, SystemSet},
+    system::Res,
+    world::World,
+};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+use bevy_time::Time;
+
+#[cfg(feature = "bevy_reflect")]
+use crate::debug::registry::{reflect_component, reflect_resource};
+use crate::{
+    debug::{StateDebugSettings, systems_in_set},
+    next_state::TriggerStateFlush,
+    schedule::{ApplyFlushSet, ResolveStateSet, StateFlush, resolve_state::can_flush},
+    state::State,
+};
+
+/// A [`Resource`] that records the most recent state flushes across all state types.
+///
+/// Only the most recent [`Self::capacity`] records are kept. Use the [`Display`] impl to dump
+/// the history as text (e.g. to a file).
+#[derive(Resource, Debug)]
+pub struct StateFlushHistory {
+    /// The maximum number of records to keep.
+    pub capacity: usize,
+    records: VecDeque<StateFlushRecord>,
+}
+
+impl Default for StateFlushHistory {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+impl StateFlushHistory {
+    /// Create an empty `StateFlushHistory` that keeps up to `capacity` records.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: VecDeque::new(),
+        }
+    }
+
+    /// Add a record, discarding the oldest record if at capacity.
+    pub(crate) fn push(&mut self, record: StateFlushRecord) {
+        while self.records.len() >= self.capacity.max(1) {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    /// Iterate over the records from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &StateFlushRecord> {
+        self.records.iter()
+    }
+
+    /// Iterate over the records for the [`State`] type `S` from oldest to newest.
+    pub fn iter_state<S: State>(&self) -> impl DoubleEndedIterator<Item = &StateFlushRecord> {
+        self.iter().filter(|x| x.type_id == TypeId::of::<S>())
+    }
+
+    /// The number of records.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Check if there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Remove all records.
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+}
+
+impl Display for StateFlushHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for record in &self.records {
+            writeln!(f, "{record}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A single state flush in the [`StateFlushHistory`].
+#[derive(Clone, Debug)]
+pub struct StateFlushRecord {
+    /// The [`FrameCount`] when the flush occurred.
+    pub frame: u32,
+    /// The elapsed [`Time`] when the flush occurred.
+    pub elapsed: Duration,
+    /// The type name of the state type.
+    pub type_name: &'static str,
+    /// The [`TypeId`] of the state type.
+    pub type_id: TypeId,
+    /// The entity with the local state, or `None` for the global state.
+    pub entity: Option<Entity>,
+    /// The reflected [`Debug`](core::fmt::Debug) representation of the old state, or `None` if disabled.
+    pub old: Option<String>,
+    /// The reflected [`Debug`](core::fmt::Debug) representation of the new state, or `None` if disabled.
+    pub new: Option<String>,
+    /// The names of the systems that ran in [`ResolveStateSet::Exit`].
+    pub exit: Vec<String>,
+    /// The names of the systems that ran in [`ResolveStateSet::Trans`].
+    pub trans: Vec<String>,
+    /// The names of the systems that ran in [`ResolveStateSet::Enter`].
+    pub enter: Vec<String>,
+    /// Whether the new state has yet to be filled in.
+    pending_new: bool,
+    /// The change tick when the flush was recorded and the hook system sets, if the hook
+    /// systems have yet to be filled in.
+    pending: Option<(Tick, [Interned<dyn SystemSet>; 3])>,
+}
+
+impl StateFlushRecord {
+    fn new<S: State>(
+        frame: u32,
+        elapsed: Duration,
+        tick: Tick,
+        entity: Option<Entity>,
+        old: Option<String>,
+    ) -> Self {
+        Self {
+            frame,
+            elapsed,
+            type_name: type_name::<S>(),
+            type_id: TypeId::of::<S>(),
+            entity,
+            old,
+            new: None,
+            exit: Vec::new(),
+            trans: Vec::new(),
+            enter: Vec::new(),
+            pending_new: true,
+            pending: Some((
+                tick,
+                [
+                    ResolveStateSet::<S>::Exit.intern(),
+                    ResolveStateSet::<S>::Trans.intern(),
+                    ResolveStateSet::<S>::Enter.intern(),
+                ],
+            )),
+        }
+    }
+}
+
+impl Display for StateFlushRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (frame, elapsed, ty) = (self.frame, self.elapsed, self.type_name);
+        let old = self.old.as_deref().unwrap_or("disabled");
+        let new = self.new.as_deref().unwrap_or("disabled");
+        write!(f, "[Frame {frame}] [{elapsed:?}] {ty} flush")?;
+        if let Some(entity) = self.entity {
+            write!(f, " ({entity})")?;
+        }
+        write!(f, ": {old} -> {new}")?;
+        for (set, names) in [
+            ("exit", &self.exit),
+            ("trans", &self.trans),
+            ("enter", &self.enter),
+        ] {
+            if !names.is_empty() {
+                write!(f, "\n    {set}: {}", names.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Add systems to record each flush of the [`State`] type `S` in the [`StateFlushHistory`] to
+/// a schedule.
+///
+/// Used in [`StateFlushHistoryPlugin`].
+pub(crate) fn schedule_record_flush<S: State>(schedule: &mut Schedule) {
+    let record_flush = |x: Option<Res<StateDebugSettings>>| x.is_some_and(|x| x.record_flush);
+    schedule.add_systems((
+        record_state_flush::<S>
+            .in_set(ResolveStateSet::<S>::Resolve)
+            .after(ResolveStateSet::<S>::Guard)
+            .before(ResolveStateSet::<S>::Flush)
+            .run_if(record_flush),
+        // The flush is applied outside of `ResolveStateSet::<S>::Resolve`, so check if `S` can
+        // flush here directly.
+        record_new_state::<S>
+            .after(ApplyFlushSet)
+            .run_if(record_flush.and(can_flush::<S>)),
+    ));
+}
+
+/// Record the old state for each triggered flush of the [`State`] type `S`.
+fn record_state_flush<S: State>(world: &mut World) {
+    if !world.contains_resource::<StateFlushHistory>() {
+        return;
+    }
+
+    let mut entities = vec![];
+    if world
+        .get_resource::<TriggerStateFlush<S>>()
+        .is_some_and(|x| x.0)
+    {
+        entities.push(None);
+    }
+    if world
+        .get_resource::<StateDebugSettings>()
+        .is_some_and(|x| x.record_local)
+    {
+        entities.extend(
+            world
+                .query::<(Entity, &TriggerStateFlush<S>)>()
+                .iter(world)
+                .filter(|(_, trigger)| trigger.0)
+                .map(|(entity, _)| Some(entity)),
+        );
+    }
+
+    let frame = world.get_resource::<FrameCount>().map_or(0, |x| x.0);
+    let elapsed = world
+        .get_resource::<Time>()
+        .map_or(Duration::ZERO, |x| x.elapsed());
+    let tick = world.change_tick();
+    let records = entities
+        .into_iter()
+        .map(|entity| {
+            let old = format_state::<S>(world, entity);
+            StateFlushRecord::new::<S>(frame, elapsed, tick, entity, old)
+        })
+        .collect::<Vec<_>>();
+
+    let mut history = world.resource_mut::<StateFlushHistory>();
+    for record in records {
+        history.push(record);
+    }
+}
+
+/// Fill in the new state for each record of the [`State`] type `S` after the flush is applied.
+fn record_new_state<S: State>(world: &mut World) {
+    world.try_resource_scope(|world, mut history: Mut<StateFlushHistory>| {
+        let type_id = TypeId::of::<S>();
+        for record in history.records.iter_mut().rev() {
+            if record.type_id != type_id || !record.pending_new {
+                continue;
+            }
+            record.new = format_state::<S>(world, record.entity);
+            record.pending_new = false;
+        }
+    });
+}
+
+/// Format the current value of the [`State`] type `S` (on an entity for local state) through
+/// reflection, or `None` if disabled.
+///
+/// Falls back to `"enabled"` if the state type isn't reflectable.
+fn format_state<S: State>(world: &World, entity: Option<Entity>) -> Option<String> {
+    let type_id = TypeId::of::<S>();
+    let enabled = match entity {
+        Some(entity) => world
+            .get_entity(entity)
+            .is_ok_and(|x| x.contains_type_id(type_id)),
+        None => world.contains_resource::<S>(),
+    };
+    if !enabled {
+        return None;
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    if let Some(value) = reflect_state(world, type_id, entity) {
+        return Some(format!("{value:?}"));
+    }
+
+    Some("enabled".to_string())
+}
+
+#[cfg(feature = "bevy_reflect")]
+fn reflect_state(world: &World, type_id: TypeId, entity: Option<Entity>) -> Option<&dyn Reflect> {
+    match entity {
+        Some(entity) => reflect_component(world, type_id)?.reflect(world.get_entity(entity).ok()?),
+        None => reflect_resource(world, type_id)?.reflect(world).ok(),
+    }
+}
+
+/// Fill in the hook systems that ran for each new record in the [`StateFlushHistory`].
+///
+/// A hook system ran if it was last run after the flush was recorded, so this must run after
+/// the [`StateFlush`] schedule and before it runs again.
+pub(crate) fn update_state_flush_history(world: &mut World) {
+    world.try_resource_scope(|world, mut history: Mut<StateFlushHistory>| {
+        let Some(schedule) = world.resource::<Schedules>().get(StateFlush) else {
+            return;
+        };
+        let this_run = world.read_change_tick();
+
+        for record in history.records.iter_mut().rev() {
+            let Some((tick, [exit, trans, enter])) = record.pending.take() else {
+                break;
+            };
+            record.exit = ran_since(schedule, exit, tick, this_run);
+            record.trans = ran_since(schedule, trans, tick, this_run);
+            record.enter = ran_since(schedule, enter, tick, this_run);
+        }
+    });
+}
+
+/// Get the names of the systems in a system set that ran since `tick`.
+fn ran_since(
+    schedule: &Schedule,
+    set: Interned<dyn SystemSet>,
+    tick: Tick,
+    this_run: Tick,
+) -> Vec<String> {
+    systems_in_set(schedule, &*set)
+        .into_iter()
+        .filter(|system| system.get_last_run().is_newer_than(tick, this_run))
+        .map(|system| system.name().to_string())
+        .collect()
+}
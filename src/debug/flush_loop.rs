@@ -0,0 +1,184 @@
+//! Detect a state that flushes every frame in a cycle (e.g. `A -> B -> A -> B`).
+//!
+//! This usually means that two states are driving each other, such as through
+//! [`ResolveStateSet::Compute`] systems. A state that flushes to the same value every frame
+//! (e.g. a per-frame refresh) isn't considered a loop.
+//!
+//! Set [`StateDebugSettings::loop_frames`] to enable flush loop detection for each state type
+//! with [`FlushLoopPlugin`]. A detected loop will be logged as a warning and sent as a
+//! [`StateFlushLoopDetected`] event.
+//!
+//! Flush loop detection requires the [`FrameCount`] resource (added by
+//! [`FrameCountPlugin`](bevy_diagnostic::FrameCountPlugin), e.g. in `MinimalPlugins`) to tell
+//! consecutive frames apart, and does nothing without it.
+//!
+//! # Example
+//!
+//! Opt in to the [`FlushLoopPlugin`] for `Screen` by adding `#[state(detect_loop)]`:
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use pyri_state::{debug::StateDebugSettings, prelude::*};
+//! #
+//! #[derive(State, Clone, PartialEq, Eq, Debug)]
+//! #[state(detect_loop)]
+//! enum Screen {
+//!     Title,
+//!     Gameplay,
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins((MinimalPlugins, StatePlugin))
+//!     .insert_resource(StateDebugSettings {
+//!         loop_frames: 10,
+//!         ..default()
+//!     })
+//!     .add_state::<Screen>();
+//! ```
+
+#[cfg(feature = "bevy_app")]
+pub use app::*;
+
+#[cfg(feature = "bevy_app")]
+mod app {
+    use core::{fmt::Debug, marker::PhantomData};
+
+    use bevy_app::{App, Plugin};
+
+    use crate::{schedule::StateFlush, state::State};
+
+    use super::{StateFlushLoopDetected, schedule_flush_loop};
+
+    /// A plugin that adds a flush loop detection system for the [`State`] type `S`.
+    ///
+    /// Calls [`schedule_flush_loop<S>`].
+    pub struct FlushLoopPlugin<S: State + Clone + PartialEq + Debug>(PhantomData<S>);
+
+    impl<S: State + Clone + PartialEq + Debug> Plugin for FlushLoopPlugin<S> {
+        fn build(&self, app: &mut App) {
+            app.add_event::<StateFlushLoopDetected<S>>();
+            schedule_flush_loop::<S>(app.get_schedule_mut(StateFlush).unwrap());
+        }
+    }
+
+    impl<S: State + Clone + PartialEq + Debug> Default for FlushLoopPlugin<S> {
+        fn default() -> Self {
+            Self(PhantomData)
+        }
+    }
+}
+
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{any::type_name, fmt::Debug};
+
+use bevy_diagnostic::FrameCount;
+use bevy_ecs::{
+    event::{Event, EventWriter},
+    schedule::{Condition, IntoScheduleConfigs, Schedule, common_conditions::resource_exists},
+    system::{Local, Res},
+};
+use bevy_log::warn;
+
+use crate::{access::FlushRef, debug::StateDebugSettings, schedule::ResolveStateSet, state::State};
+
+/// An event sent when the [`State`] type `S` is detected to be flushing in a loop.
+///
+/// Added by [`FlushLoopPlugin<S>`].
+#[derive(Event, Clone, Debug)]
+pub struct StateFlushLoopDetected<S: State> {
+    /// The repeating cycle of new states, where `None` is disabled.
+    pub cycle: Vec<Option<S>>,
+    /// The number of consecutive frames the cycle repeated for.
+    pub frames: u32,
+}
+
+/// The recent flushes of the [`State`] type `S` in consecutive frames.
+struct FlushLoopTracker<S: State> {
+    last_frame: Option<u32>,
+    values: VecDeque<Option<S>>,
+}
+
+impl<S: State> Default for FlushLoopTracker<S> {
+    fn default() -> Self {
+        Self {
+            last_frame: None,
+            values: VecDeque::new(),
+        }
+    }
+}
+
+impl<S: State + PartialEq> FlushLoopTracker<S> {
+    /// Find the shortest period of at most `max_period` that the values repeat with.
+    ///
+    /// A period of 1 (the same value every frame) is a refresh, not a loop, so it's ignored.
+    fn period(&self, max_period: usize) -> Option<usize> {
+        let values = &self.values;
+        (1..=max_period)
+            .find(|&p| (p..values.len()).all(|i| values[i] == values[i - p]))
+            .filter(|&p| p >= 2)
+    }
+}
+
+fn detect_state_flush_loop<S: State + Clone + PartialEq + Debug>(
+    frame: Res<FrameCount>,
+    settings: Res<StateDebugSettings>,
+    mut tracker: Local<FlushLoopTracker<S>>,
+    mut events: EventWriter<StateFlushLoopDetected<S>>,
+    state: FlushRef<S>,
+) {
+    let frame = frame.0;
+    let frames = settings.loop_frames as usize;
+
+    // Start over if a frame was skipped.
+    if !matches!(tracker.last_frame, Some(x) if frame.wrapping_sub(x) <= 1) {
+        tracker.values.clear();
+    }
+    tracker.last_frame = Some(frame);
+    tracker.values.push_back(state.get().1.cloned());
+    while tracker.values.len() > frames {
+        tracker.values.pop_front();
+    }
+    if tracker.values.len() < frames {
+        return;
+    }
+
+    let max_period = match settings.loop_period as usize {
+        0 => frames / 2,
+        x => x.min(frames / 2),
+    };
+    let Some(period) = tracker.period(max_period) else {
+        return;
+    };
+
+    let ty = type_name::<S>();
+    let cycle = tracker
+        .values
+        .iter()
+        .take(period)
+        .cloned()
+        .collect::<Vec<_>>();
+    warn!("[Frame {frame}] {ty} flush loop detected over {frames} frames: {cycle:?}");
+    events.write(StateFlushLoopDetected {
+        cycle,
+        frames: frames as u32,
+    });
+
+    // Avoid reporting the same loop every frame.
+    tracker.values.clear();
+}
+
+/// Add a flush loop detection system for the [`State`] type `S` to a schedule.
+///
+/// Used in [`FlushLoopPlugin<S>`].
+pub fn schedule_flush_loop<S: State + Clone + PartialEq + Debug>(schedule: &mut Schedule) {
+    schedule.add_systems(
+        detect_state_flush_loop::<S>
+            .after(ResolveStateSet::<S>::Guard)
+            .before(ResolveStateSet::<S>::Flush)
+            .run_if(
+                S::is_triggered
+                    .and(|x: Option<Res<StateDebugSettings>>| x.is_some_and(|x| x.loop_frames > 0))
+                    .and(resource_exists::<FrameCount>),
+            ),
+    );
+}
@@ -33,7 +33,8 @@ mod app {
         }
     }
 
-    /// A plugin that adds local on-flush logging systems for the [`State`] type `S`.
+    /// A plugin that adds local on-flush logging systems for the [`State`]
+    /// type `S`.
     ///
     /// Calls [`schedule_local_log_flush<S>`].
     pub struct LocalLogFlushPlugin<S: LocalState + Debug>(PhantomData<S>);
@@ -56,6 +57,7 @@ use core::{any::type_name, fmt::Debug};
 use bevy_diagnostic::FrameCount;
 use bevy_ecs::{
     entity::Entity,
+    event::EventReader,
     schedule::{Condition, IntoScheduleConfigs, Schedule},
     system::{Query, Res, StaticSystemParam},
 };
@@ -65,8 +67,8 @@ use crate::{
     access::{CurrentRef, FlushRef, NextRef},
     debug::StateDebugSettings,
     next_state::{NextState, TriggerStateFlush},
-    pattern::{StatePattern, StateTransPattern},
-    schedule::ResolveStateSet,
+    pattern::StateTransPattern,
+    schedule::{ResolveStateSet, resolve_state::StateFlushRejected},
     state::{LocalState, State},
 };
 
@@ -98,24 +100,45 @@ fn log_state_enter<S: State + Debug>(frame: Res<FrameCount>, new: NextRef<S>) {
     info!("[Frame {frame}] {ty} enter: {new:?}");
 }
 
+fn log_state_reject<S: State + Debug>(
+    frame: Res<FrameCount>,
+    mut events: EventReader<StateFlushRejected<S>>,
+) {
+    let frame = frame.0;
+    let ty = type_name::<S>();
+    for event in events.read() {
+        let (old, new, guard) = (&event.old, &event.new, &event.guard);
+        match event.entity {
+            Some(entity) => {
+                info!("[Frame {frame}] {ty} reject ({entity}): {old:?} -> {new:?} by {guard}")
+            }
+            None => info!("[Frame {frame}] {ty} reject: {old:?} -> {new:?} by {guard}"),
+        }
+    }
+}
+
 /// Add on-flush logging systems for the [`State`] type `S` to a schedule.
 ///
 /// Used in [`LogFlushPlugin<S>`].
 pub fn schedule_log_flush<S: State + Debug>(schedule: &mut Schedule) {
     schedule.add_systems((
         log_state_flush::<S>
-            .after(ResolveStateSet::<S>::Trigger)
+            .after(ResolveStateSet::<S>::Guard)
             .before(ResolveStateSet::<S>::Flush)
             .run_if(
                 S::is_triggered
                     .and(|x: Option<Res<StateDebugSettings>>| x.is_some_and(|x| x.log_flush)),
             ),
+        log_state_reject::<S>
+            .after(ResolveStateSet::<S>::Guard)
+            .before(ResolveStateSet::<S>::Flush)
+            .run_if(|x: Option<Res<StateDebugSettings>>| x.is_some_and(|x| x.log_reject)),
         log_state_exit::<S>
             .in_set(ResolveStateSet::<S>::Flush)
             .before(ResolveStateSet::<S>::Exit)
             .run_if(
                 S::is_triggered
-                    .and(S::ANY.will_exit())
+                    .and(S::is_enabled)
                     .and(|x: Option<Res<StateDebugSettings>>| x.is_some_and(|x| x.log_exit)),
             ),
         log_state_trans::<S>
@@ -131,7 +154,7 @@ pub fn schedule_log_flush<S: State + Debug>(schedule: &mut Schedule) {
             .before(ResolveStateSet::<S>::Enter)
             .run_if(
                 S::is_triggered
-                    .and(S::ANY.will_enter())
+                    .and(S::will_be_enabled)
                     .and(|x: Option<Res<StateDebugSettings>>| x.is_some_and(|x| x.log_enter)),
             ),
     ));
@@ -213,7 +236,7 @@ fn log_local_state_enter<S: LocalState + Debug>(
 pub fn schedule_local_log_flush<S: LocalState + Debug>(schedule: &mut Schedule) {
     schedule.add_systems((
         log_local_state_flush::<S>
-            .after(ResolveStateSet::<S>::Trigger)
+            .after(ResolveStateSet::<S>::Guard)
             .before(ResolveStateSet::<S>::Flush)
             .run_if(|x: Option<Res<StateDebugSettings>>| {
                 x.is_some_and(|x| x.log_local && x.log_flush)
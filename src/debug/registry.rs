@@ -0,0 +1,245 @@
+//! Inspect every registered [`State`] type generically with a [`StateRegistry`].
+//!
+//! Every state type added with [`AppExtState`](crate::setup::AppExtState) is registered
+//! automatically. The [derive macro](pyri_state_derive::State) also records its enabled plugins
+//! and local state support, and a manual [`RegisterState`](crate::setup::RegisterState) impl can
+//! call [`StateRegistry::register`] to do the same.
+//!
+//! To access the current and next values through reflection, the state type and its
+//! [`NextState`](crate::next_state::NextState) type must be registered in the
+//! [`AppTypeRegistry`] with [`ReflectResource`] (or [`ReflectComponent`] for local states).
+//!
+//! # Example
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use pyri_state::{debug::registry::StateRegistry, prelude::*};
+//! #
+//! #[derive(State, Reflect, Clone, PartialEq, Eq, Default)]
+//! #[reflect(Resource)]
+//! enum Screen {
+//!     #[default]
+//!     Title,
+//!     Gameplay,
+//! }
+//!
+//! fn list_states(world: &World) {
+//!     for info in world.resource::<StateRegistry>().iter() {
+//!         let current = info.current(world);
+//!         info!("{}: {current:?} (plugins: {:?})", info.type_name, info.plugins);
+//!     }
+//! }
+//! #
+//! # let mut app = App::new();
+//! # app.add_plugins(StatePlugin).init_state::<Screen>().register_type::<Screen>();
+//! # app.world_mut().run_system_cached(list_states).unwrap();
+//! ```
+
+use alloc::vec::Vec;
+use core::any::{TypeId, type_name};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_ecs::{
+    change_detection::Mut,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+};
+use bevy_ecs::{entity::Entity, resource::Resource, schedule::Schedule, world::World};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+
+use crate::{
+    debug::flush_history::schedule_record_flush,
+    next_state::TriggerStateFlush,
+    state::{LocalState, State},
+};
+
+/// A [`Resource`] that lists every registered [`State`] type.
+#[derive(Resource, Default, Debug)]
+pub struct StateRegistry(Vec<StateInfo>);
+
+impl StateRegistry {
+    /// Register a [`State`] type with its [`StateInfo`], replacing any previous registration.
+    pub fn register(world: &mut World, info: StateInfo) {
+        let mut registry = world.get_resource_or_init::<Self>();
+        if let Some(old) = registry.0.iter_mut().find(|x| x.type_id == info.type_id) {
+            *old = info;
+        } else {
+            registry.0.push(info);
+        }
+    }
+
+    /// Register the [`State`] type `S` with a default [`StateInfo`] if it's not already
+    /// registered.
+    pub(crate) fn register_default<S: State>(world: &mut World) {
+        if world.get_resource_or_init::<Self>().get::<S>().is_none() {
+            Self::register(world, StateInfo::new::<S>());
+        }
+    }
+
+    /// Get the [`StateInfo`] for the [`State`] type `S`, or `None` if not registered.
+    pub fn get<S: State>(&self) -> Option<&StateInfo> {
+        self.get_by_id(TypeId::of::<S>())
+    }
+
+    /// Get the [`StateInfo`] for a [`State`] type by its [`TypeId`], or `None` if not
+    /// registered.
+    pub fn get_by_id(&self, type_id: TypeId) -> Option<&StateInfo> {
+        self.0.iter().find(|x| x.type_id == type_id)
+    }
+
+    /// Iterate over the [`StateInfo`] of every registered [`State`] type in order of
+    /// registration.
+    pub fn iter(&self) -> impl Iterator<Item = &StateInfo> {
+        self.0.iter()
+    }
+}
+
+/// Information about a registered [`State`] type in the [`StateRegistry`].
+#[derive(Clone, Debug)]
+pub struct StateInfo {
+    /// The type name of the state type.
+    pub type_name: &'static str,
+    /// The [`TypeId`] of the state type.
+    pub type_id: TypeId,
+    /// The type name of the [`NextState`](crate::next_state::NextState) type.
+    pub next_type_name: &'static str,
+    /// The [`TypeId`] of the [`NextState`](crate::next_state::NextState) type.
+    pub next_type_id: TypeId,
+    /// Whether the state type supports local state.
+    pub local: bool,
+    /// The names of the enabled plugins (e.g. `"detect_change"`).
+    pub plugins: Vec<&'static str>,
+    trigger: fn(&mut World),
+    trigger_local: Option<fn(&mut World, Entity)>,
+    record_flush: fn(&mut Schedule),
+}
+
+impl StateInfo {
+    /// Create a `StateInfo` for the [`State`] type `S`.
+    pub fn new<S: State>() -> Self {
+        Self {
+            type_name: type_name::<S>(),
+            type_id: TypeId::of::<S>(),
+            next_type_name: type_name::<S::Next>(),
+            next_type_id: TypeId::of::<S::Next>(),
+            local: false,
+            plugins: Vec::new(),
+            trigger: |world| world.get_resource_or_init::<TriggerStateFlush<S>>().0 = true,
+            trigger_local: None,
+            record_flush: schedule_record_flush::<S>,
+        }
+    }
+
+    /// Create a `StateInfo` for the local [`State`] type `S`.
+    pub fn new_local<S: LocalState>() -> Self {
+        Self {
+            local: true,
+            trigger_local: Some(|world, entity| {
+                if let Some(mut trigger) = world.get_mut::<TriggerStateFlush<S>>(entity) {
+                    trigger.0 = true;
+                }
+            }),
+            ..Self::new::<S>()
+        }
+    }
+
+    /// Set the names of the enabled plugins.
+    pub fn with_plugins(mut self, plugins: impl IntoIterator<Item = &'static str>) -> Self {
+        self.plugins = plugins.into_iter().collect();
+        self
+    }
+
+    /// Trigger the state to flush in the [`StateFlush`](crate::schedule::StateFlush) schedule.
+    pub fn trigger(&self, world: &mut World) {
+        (self.trigger)(world);
+    }
+
+    /// Trigger the local state on an entity to flush in the
+    /// [`StateFlush`](crate::schedule::StateFlush) schedule.
+    ///
+    /// Does nothing if the state type doesn't support local state.
+    pub fn trigger_local(&self, world: &mut World, entity: Entity) {
+        if let Some(trigger_local) = self.trigger_local {
+            trigger_local(world, entity);
+        }
+    }
+
+    /// Add systems to record each flush of the state in the
+    /// [`StateFlushHistory`](crate::debug::flush_history::StateFlushHistory) to a schedule.
+    #[cfg(feature = "bevy_app")]
+    pub(crate) fn schedule_record_flush(&self, schedule: &mut Schedule) {
+        (self.record_flush)(schedule);
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl StateInfo {
+    /// Get the current state through reflection, or `None` if disabled or not reflectable.
+    pub fn current<'w>(&self, world: &'w World) -> Option<&'w dyn Reflect> {
+        reflect_resource(world, self.type_id)?.reflect(world).ok()
+    }
+
+    /// Get the current state mutably through reflection, or `None` if disabled or not
+    /// reflectable.
+    ///
+    /// NOTE: Don't mutate the current state directly unless you know what you're doing.
+    pub fn current_mut<'w>(&self, world: &'w mut World) -> Option<Mut<'w, dyn Reflect>> {
+        reflect_resource(world, self.type_id)?
+            .reflect_mut(world)
+            .ok()
+    }
+
+    /// Get the [`NextState`](crate::next_state::NextState) through reflection, or `None` if
+    /// not reflectable.
+    pub fn next<'w>(&self, world: &'w World) -> Option<&'w dyn Reflect> {
+        reflect_resource(world, self.next_type_id)?
+            .reflect(world)
+            .ok()
+    }
+
+    /// Get the [`NextState`](crate::next_state::NextState) mutably through reflection, or
+    /// `None` if not reflectable.
+    ///
+    /// Use [`Self::trigger`] to flush the change.
+    pub fn next_mut<'w>(&self, world: &'w mut World) -> Option<Mut<'w, dyn Reflect>> {
+        reflect_resource(world, self.next_type_id)?
+            .reflect_mut(world)
+            .ok()
+    }
+
+    /// Get the current local state on an entity through reflection, or `None` if disabled or
+    /// not reflectable.
+    pub fn current_local<'w>(&self, world: &'w World, entity: Entity) -> Option<&'w dyn Reflect> {
+        reflect_component(world, self.type_id)?.reflect(world.get_entity(entity).ok()?)
+    }
+
+    /// Get the local [`NextState`](crate::next_state::NextState) on an entity through
+    /// reflection, or `None` if not reflectable.
+    pub fn next_local<'w>(&self, world: &'w World, entity: Entity) -> Option<&'w dyn Reflect> {
+        reflect_component(world, self.next_type_id)?.reflect(world.get_entity(entity).ok()?)
+    }
+
+    /// Get the local [`NextState`](crate::next_state::NextState) on an entity mutably through
+    /// reflection, or `None` if not reflectable.
+    ///
+    /// Use [`Self::trigger_local`] to flush the change.
+    pub fn next_local_mut<'w>(
+        &self,
+        world: &'w mut World,
+        entity: Entity,
+    ) -> Option<Mut<'w, dyn Reflect>> {
+        reflect_component(world, self.next_type_id)?.reflect_mut(world.get_entity_mut(entity).ok()?)
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+pub(crate) fn reflect_resource(world: &World, type_id: TypeId) -> Option<ReflectResource> {
+    let registry = world.get_resource::<AppTypeRegistry>()?.read();
+    registry.get_type_data::<ReflectResource>(type_id).cloned()
+}
+
+#[cfg(feature = "bevy_reflect")]
+pub(crate) fn reflect_component(world: &World, type_id: TypeId) -> Option<ReflectComponent> {
+    let registry = world.get_resource::<AppTypeRegistry>()?.read();
+    registry.get_type_data::<ReflectComponent>(type_id).cloned()
+}
@@ -2,7 +2,15 @@
 
 #[cfg(feature = "bevy_state")]
 pub mod bevy_state;
+#[cfg(feature = "delay")]
+pub mod delay;
+#[cfg(feature = "history")]
+pub mod history;
 #[cfg(feature = "react")]
 pub mod react;
+#[cfg(feature = "sequence_asset")]
+pub mod sequence_asset;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
 #[cfg(feature = "split")]
 pub mod split;
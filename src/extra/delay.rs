@@ -0,0 +1,214 @@
+//! Schedule a state flush to occur after a delay.
+//!
+//! Enable the `delay` feature flag to use this module.
+//!
+//! Delays are measured in virtual time, so pausing [`Time<Virtual>`](bevy_time::Virtual) will
+//! also pause pending state flushes. Delays are ticked once per frame in
+//! [`PreUpdate`](bevy_app::PreUpdate), so they aren't affected by how many times the
+//! [`StateFlush`](crate::schedule::StateFlush) schedule runs per frame.
+//!
+//! # Example
+//!
+//! Opt in to the [`DelayPlugin`] for `Phase` by adding `#[state(delay)]`:
+//!
+//! ```
+//! # use core::time::Duration;
+//! #
+//! # use bevy::prelude::*;
+//! # use pyri_state::prelude::*;
+//! #
+//! #[derive(State, Clone, PartialEq, Eq)]
+//! #[state(delay)]
+//! enum Phase {
+//!     Battle,
+//!     Victory,
+//!     Results,
+//! }
+//!
+//! # fn plugin(app: &mut App) {
+//! // Enter `Phase::Results` 3 seconds after entering `Phase::Victory`.
+//! app.add_systems(
+//!     StateFlush,
+//!     Phase::Victory.on_enter(Phase::Results.enter_after(Duration::from_secs(3))),
+//! );
+//! # }
+//! ```
+
+#[cfg(feature = "bevy_app")]
+pub use app::*;
+
+#[cfg(feature = "bevy_app")]
+mod app {
+    use core::marker::PhantomData;
+
+    use bevy_app::{App, Plugin, PreUpdate};
+
+    use crate::{schedule::StateFlush, state::StateMut};
+
+    use super::{DelayedStateFlush, schedule_delay, schedule_delay_tick};
+
+    /// A plugin that adds a [`DelayedStateFlush<S>`] resource and systems to apply it for the
+    /// [`State`](crate::state::State) type `S` to the [`PreUpdate`] and [`StateFlush`]
+    /// schedules.
+    ///
+    /// Calls [`schedule_delay_tick<S>`] and [`schedule_delay<S>`].
+    pub struct DelayPlugin<S: StateMut>(PhantomData<S>);
+
+    impl<S: StateMut> Plugin for DelayPlugin<S> {
+        fn build(&self, app: &mut App) {
+            app.init_resource::<DelayedStateFlush<S>>();
+            app.edit_schedule(PreUpdate, schedule_delay_tick::<S>);
+            schedule_delay::<S>(app.get_schedule_mut(StateFlush).unwrap());
+        }
+    }
+
+    impl<S: StateMut> Default for DelayPlugin<S> {
+        fn default() -> Self {
+            Self(PhantomData)
+        }
+    }
+}
+
+use core::time::Duration;
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_ecs::reflect::ReflectResource;
+use bevy_ecs::{
+    resource::Resource,
+    schedule::{IntoScheduleConfigs as _, Schedule},
+    system::{Res, ResMut},
+};
+use bevy_time::{Time, Timer, TimerMode};
+
+use crate::{
+    access::NextMut,
+    schedule::ResolveStateSet,
+    state::{State, StateMut},
+};
+
+/// A [`Resource`] that stores a pending flush of the [`State`] type `S` that will be
+/// triggered after a delay.
+///
+/// The pending flush will be canceled if `S` flushes before the delay elapses.
+#[derive(Resource, Debug)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Resource)
+)]
+pub struct DelayedStateFlush<S: State>(
+    /// The remaining delay and the next state, or `None` if there's no pending flush.
+    pub Option<(Timer, Option<S>)>,
+);
+
+impl<S: State> Default for DelayedStateFlush<S> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<S: State> DelayedStateFlush<S> {
+    /// Schedule a flush to a new value, or `None` to disable, after a delay.
+    ///
+    /// This will replace any existing pending flush.
+    pub fn set_after(&mut self, state: Option<S>, delay: Duration) {
+        self.0 = Some((Timer::new(delay, TimerMode::Once), state));
+    }
+
+    /// Schedule a flush to disable the state after a delay.
+    pub fn disable_after(&mut self, delay: Duration) {
+        self.set_after(None, delay);
+    }
+
+    /// Schedule a flush to enable the state with a specific value after a delay.
+    pub fn enter_after(&mut self, value: S, delay: Duration) {
+        self.set_after(Some(value), delay);
+    }
+
+    /// Cancel the pending flush.
+    pub fn cancel(&mut self) {
+        self.0 = None;
+    }
+
+    /// Check if there's a pending flush.
+    pub fn is_pending(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Get the remaining delay of the pending flush, or `None` if there's no pending flush.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.0.as_ref().map(|(timer, _)| timer.remaining())
+    }
+}
+
+/// An extension trait for [`StateMut`] types that provides delayed flush systems.
+pub trait StateMutExtDelay: StateMut {
+    /// A system that cancels the pending delayed flush.
+    fn cancel_delay(mut delay: ResMut<DelayedStateFlush<Self>>) {
+        delay.cancel();
+    }
+
+    /// Build a system that schedules a flush to disable the state after a delay.
+    fn disable_after(
+        delay: Duration,
+    ) -> impl 'static + Send + Sync + Fn(ResMut<DelayedStateFlush<Self>>) {
+        move |mut pending| pending.disable_after(delay)
+    }
+}
+
+impl<S: StateMut> StateMutExtDelay for S {}
+
+/// An extension trait for [`StateMut`] types that also implement [`Clone`] that provides
+/// delayed flush systems.
+pub trait StateMutExtDelayClone: StateMut + Clone {
+    /// Build a system that schedules a flush to enable the state with a specific value after a
+    /// delay.
+    fn enter_after(
+        self,
+        delay: Duration,
+    ) -> impl 'static + Send + Sync + Fn(ResMut<DelayedStateFlush<Self>>) {
+        move |mut pending| pending.enter_after(self.clone(), delay)
+    }
+}
+
+impl<S: StateMut + Clone> StateMutExtDelayClone for S {}
+
+fn tick_delayed_flush<S: StateMut>(
+    time: Res<Time>,
+    mut delay: ResMut<DelayedStateFlush<S>>,
+    mut state: NextMut<S>,
+) {
+    let Some((timer, _)) = delay.0.as_mut() else {
+        return;
+    };
+    if !timer.tick(time.delta()).finished() {
+        return;
+    }
+
+    let (_, next) = delay.0.take().unwrap();
+    state.trigger().set(next);
+}
+
+fn cancel_delayed_flush<S: State>(mut delay: ResMut<DelayedStateFlush<S>>) {
+    delay.0 = None;
+}
+
+/// Add a system to tick [`DelayedStateFlush<S>`] for the [`State`] type `S` to a schedule
+/// that runs once per frame before the [`StateFlush`](crate::schedule::StateFlush) schedule.
+///
+/// Used in [`DelayPlugin<S>`].
+pub fn schedule_delay_tick<S: StateMut>(schedule: &mut Schedule) {
+    schedule.add_systems(tick_delayed_flush::<S>);
+}
+
+/// Add a system to cancel [`DelayedStateFlush<S>`] on flush for the [`State`] type `S` to a
+/// schedule.
+///
+/// Used in [`DelayPlugin<S>`].
+pub fn schedule_delay<S: StateMut>(schedule: &mut Schedule) {
+    schedule.add_systems(
+        cancel_delayed_flush::<S>
+            .in_set(ResolveStateSet::<S>::AnyFlush)
+            .before(ResolveStateSet::<S>::Exit),
+    );
+}
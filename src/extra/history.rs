@@ -0,0 +1,258 @@
+//! Record a [`StateHistory`] to navigate back and forward through previous states.
+//!
+//! Enable the `history` feature flag to use this module.
+//!
+//! Unlike [`NextStateStack`](crate::next_state::stack::NextStateStack), this works with any
+//! [mutable `NextState`](crate::next_state::NextStateMut) type.
+//!
+//! The history is only updated when a flush actually occurs, and flushes that don't change the
+//! state (e.g. refreshes) aren't recorded.
+//!
+//! # Example
+//!
+//! Opt in to the [`HistoryPlugin`] for `Menu` by adding `#[state(history = N)]`:
+//!
+//! ```
+//! # use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+//! # use pyri_state::prelude::*;
+//! #
+//! #[derive(State, Clone, PartialEq, Eq)]
+//! #[state(history = 10)]
+//! enum Menu {
+//!     Main,
+//!     Settings,
+//!     Credits,
+//! }
+//!
+//! # fn plugin(app: &mut App) {
+//! app.add_systems(Update, (
+//!     Menu::back.run_if(Menu::can_go_back.and(input_just_pressed(KeyCode::Escape))),
+//!     Menu::forward.run_if(Menu::can_go_forward.and(input_just_pressed(KeyCode::Tab))),
+//! ));
+//! # }
+//! ```
+
+#[cfg(feature = "bevy_app")]
+pub use app::*;
+
+#[cfg(feature = "bevy_app")]
+mod app {
+    use core::marker::PhantomData;
+
+    use bevy_app::{App, Plugin};
+
+    use crate::{schedule::StateFlush, state::State};
+
+    use super::{StateHistory, schedule_history};
+
+    /// A plugin that adds a [`StateHistory<S>`] resource and a system to record it for the
+    /// [`State`] type `S` to the [`StateFlush`] schedule.
+    ///
+    /// Calls [`schedule_history<S>`].
+    pub struct HistoryPlugin<S: State + Clone + PartialEq> {
+        capacity: usize,
+        _phantom: PhantomData<S>,
+    }
+
+    impl<S: State + Clone + PartialEq> Plugin for HistoryPlugin<S> {
+        fn build(&self, app: &mut App) {
+            app.insert_resource(StateHistory::<S>::new(self.capacity));
+            schedule_history::<S>(app.get_schedule_mut(StateFlush).unwrap());
+        }
+    }
+
+    impl<S: State + Clone + PartialEq> HistoryPlugin<S> {
+        /// Create a [`HistoryPlugin`] that remembers up to `capacity` previous states.
+        pub fn new(capacity: usize) -> Self {
+            Self {
+                capacity,
+                _phantom: PhantomData,
+            }
+        }
+    }
+}
+
+use alloc::{collections::VecDeque, vec::Vec};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_ecs::reflect::ReflectResource;
+use bevy_ecs::{
+    resource::Resource,
+    schedule::{IntoScheduleConfigs as _, Schedule},
+    system::{Res, ResMut},
+};
+
+use crate::{
+    access::{FlushRef, NextMut},
+    schedule::ResolveStateSet,
+    state::{State, StateMut},
+};
+
+/// A [`Resource`] that records the previous values of the [`State`] type `S` on flush.
+#[derive(Resource, Debug)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Resource)
+)]
+pub struct StateHistory<S: State> {
+    past: VecDeque<Option<S>>,
+    future: Vec<Option<S>>,
+    capacity: usize,
+    navigation: Option<Navigation>,
+}
+
+/// The direction of a pending navigation through a [`StateHistory`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+enum Navigation {
+    Back,
+    Forward,
+}
+
+impl<S: State> StateHistory<S> {
+    /// Create an empty `StateHistory` that remembers up to `capacity` previous states.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            past: VecDeque::new(),
+            future: Vec::new(),
+            capacity,
+            navigation: None,
+        }
+    }
+
+    /// Get the maximum number of previous states to remember.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Iterate over the previous states from most to least recent, with `None` for disabled.
+    pub fn past(&self) -> impl Iterator<Item = Option<&S>> {
+        self.past.iter().rev().map(Option::as_ref)
+    }
+
+    /// Iterate over the states that were navigated back from, from most to least recent, with
+    /// `None` for disabled.
+    pub fn future(&self) -> impl Iterator<Item = Option<&S>> {
+        self.future.iter().rev().map(Option::as_ref)
+    }
+
+    /// Check if there's a previous state to navigate back to.
+    pub fn can_go_back(&self) -> bool {
+        !self.past.is_empty()
+    }
+
+    /// Check if there's a state to navigate forward to.
+    pub fn can_go_forward(&self) -> bool {
+        !self.future.is_empty()
+    }
+
+    /// Forget all previous and future states.
+    pub fn clear(&mut self) {
+        self.past.clear();
+        self.future.clear();
+    }
+
+    /// Record a flush from `old` to `new`.
+    ///
+    /// If the flush completes a pending navigation, move between the past and future states.
+    /// Otherwise, push `old` and forget any future states.
+    fn record(&mut self, old: Option<S>, new: Option<&S>)
+    where
+        S: PartialEq,
+    {
+        let navigation = self.navigation.take();
+        if old.as_ref() == new {
+            return;
+        }
+
+        match navigation {
+            Some(Navigation::Back) if self.past.back().map(Option::as_ref) == Some(new) => {
+                self.past.pop_back();
+                self.future.push(old);
+            }
+            Some(Navigation::Forward) if self.future.last().map(Option::as_ref) == Some(new) => {
+                self.future.pop();
+                self.push_past(old);
+            }
+            _ => {
+                self.future.clear();
+                self.push_past(old);
+            }
+        }
+    }
+
+    /// Push a previous state, forgetting the least recent state if over capacity.
+    fn push_past(&mut self, old: Option<S>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.past.len() == self.capacity {
+            self.past.pop_front();
+        }
+        self.past.push_back(old);
+    }
+}
+
+/// An extension trait for [`StateMut`] types that also implement [`Clone`] and [`PartialEq`]
+/// that provides [`StateHistory`] navigation systems.
+pub trait StateMutExtHistory: StateMut + Clone + PartialEq {
+    /// A run condition that checks if there's a previous state to navigate back to.
+    fn can_go_back(history: Res<StateHistory<Self>>) -> bool {
+        history.can_go_back()
+    }
+
+    /// A run condition that checks if there's a state to navigate forward to.
+    fn can_go_forward(history: Res<StateHistory<Self>>) -> bool {
+        history.can_go_forward()
+    }
+
+    /// A system that sets the next state to the previous state and triggers a flush.
+    ///
+    /// The history is updated once the flush occurs, so calling this multiple times before a
+    /// flush will only navigate back once.
+    fn back(mut history: ResMut<StateHistory<Self>>, mut state: NextMut<Self>) {
+        let Some(prev) = history.past.back().cloned() else {
+            return;
+        };
+
+        history.navigation = Some(Navigation::Back);
+        state.trigger().set(prev);
+    }
+
+    /// A system that sets the next state to the state that was navigated back from and
+    /// triggers a flush.
+    ///
+    /// The history is updated once the flush occurs, so calling this multiple times before a
+    /// flush will only navigate forward once.
+    fn forward(mut history: ResMut<StateHistory<Self>>, mut state: NextMut<Self>) {
+        let Some(next) = history.future.last().cloned() else {
+            return;
+        };
+
+        history.navigation = Some(Navigation::Forward);
+        state.trigger().set(next);
+    }
+
+    /// A system that forgets all previous and future states.
+    fn clear_history(mut history: ResMut<StateHistory<Self>>) {
+        history.clear();
+    }
+}
+
+impl<S: StateMut + Clone + PartialEq> StateMutExtHistory for S {}
+
+fn record_history<S: State + Clone + PartialEq>(
+    mut history: ResMut<StateHistory<S>>,
+    state: FlushRef<S>,
+) {
+    let (old, new) = state.get();
+    history.record(old.cloned(), new);
+}
+
+/// Add a system to record [`StateHistory<S>`] for the [`State`] type `S` to a schedule.
+///
+/// Used in [`HistoryPlugin<S>`].
+pub fn schedule_history<S: State + Clone + PartialEq>(schedule: &mut Schedule) {
+    schedule.add_systems(record_history::<S>.in_set(ResolveStateSet::<S>::AnyFlush));
+}
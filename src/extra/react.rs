@@ -49,8 +49,8 @@ use bevy_ecs::{
 use bevy_render::view::visibility::Visibility;
 
 use crate::{
-    access::{CurrentRef, NextRef},
-    pattern::StatePattern as _,
+    access::{FlushRef, NextRef},
+    pattern::{BoxedStatePattern, StatePattern},
     state::State,
 };
 
@@ -67,13 +67,26 @@ pub fn schedule_react<S: State + Eq>(schedule: &mut Schedule) {
         S::ANY.on_enable((show_on_enable_state::<S>, enable_on_enable_state::<S>)),
         S::ANY.on_exit((
             despawn_on_exit_state::<S>,
-            hide_on_exit_state::<S>,
-            disable_on_exit_state::<S>,
+            hide_on_exit_state::<S, VisibleInState<S>>,
+            hide_on_exit_state::<S, VisibleInStatePattern<S>>,
+            disable_on_exit_state::<S, EnabledInState<S>>,
+            disable_on_exit_state::<S, EnabledInStatePattern<S>>,
+        )),
+        S::ANY.on_enter((
+            show_on_enter_state::<S, VisibleInState<S>>,
+            show_on_enter_state::<S, VisibleInStatePattern<S>>,
+            enable_on_enter_state::<S, EnabledInState<S>>,
+            enable_on_enter_state::<S, EnabledInStatePattern<S>>,
         )),
-        S::ANY.on_enter((show_on_enter_state::<S>, enable_on_enter_state::<S>)),
     ));
 }
 
+/// A reaction component that matches some values of the [`State`] type `S`.
+trait StateReaction<S: State>: Component {
+    /// Check if the reaction applies in a particular state.
+    fn matches(&self, state: &S) -> bool;
+}
+
 /// A component that despawns an entity on any exit of the [`State`] type `S`.
 #[derive(Component, Default)]
 #[cfg_attr(
@@ -142,6 +155,8 @@ fn despawn_on_disable_state<S: State>(
 ///
 /// - On enter, the visibility will be set to [`Visibility::Inherited`].
 /// - On exit, the visibility will be set to [`Visibility::Hidden`].
+///
+/// To show an entity while in any value that matches a pattern, use [`VisibleInStatePattern`].
 #[derive(Component, Default)]
 #[cfg_attr(
     feature = "bevy_reflect",
@@ -153,23 +168,80 @@ pub struct VisibleInState<S: State>(
     pub S,
 );
 
-fn hide_on_exit_state<S: State + Eq>(
-    state: CurrentRef<S>,
-    mut reaction_query: Query<(&mut Visibility, &VisibleInState<S>)>,
+impl<S: State + Eq> StateReaction<S> for VisibleInState<S> {
+    fn matches(&self, state: &S) -> bool {
+        &self.0 == state
+    }
+}
+
+/// A component that shows an entity while in any value of the [`State`] type `S` that matches a
+/// pattern.
+///
+/// - On enter, the visibility will be set to [`Visibility::Inherited`].
+/// - On exit, the visibility will be set to [`Visibility::Hidden`] (unless the new state also
+///   matches).
+///
+/// This component can't be reflected, since the pattern is type-erased.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use pyri_state::prelude::*;
+/// #
+/// # #[derive(State, Clone, PartialEq, Eq)]
+/// # #[state(react)]
+/// # enum Screen {
+/// #     Title,
+/// #     Gameplay,
+/// #     Pause,
+/// # }
+/// #
+/// fn spawn_hud(mut commands: Commands) {
+///     commands.spawn((
+///         Visibility::Hidden,
+///         VisibleInStatePattern::new(state!(Screen::Gameplay | Screen::Pause)),
+///     ));
+/// }
+/// ```
+#[derive(Component)]
+pub struct VisibleInStatePattern<S: State>(
+    /// The pattern of states during which the entity should be visible.
+    pub BoxedStatePattern<S>,
+);
+
+impl<S: State> VisibleInStatePattern<S> {
+    /// Create a `VisibleInStatePattern` that's visible while in a value that matches a pattern.
+    pub fn new(pattern: impl StatePattern<S>) -> Self {
+        Self(BoxedStatePattern::new(pattern))
+    }
+}
+
+impl<S: State> StateReaction<S> for VisibleInStatePattern<S> {
+    fn matches(&self, state: &S) -> bool {
+        self.0.matches(state)
+    }
+}
+
+fn hide_on_exit_state<S: State, R: StateReaction<S>>(
+    state: FlushRef<S>,
+    mut reaction_query: Query<(&mut Visibility, &R)>,
 ) {
+    // Stay visible if the new state also matches.
+    let (old, new) = state.get();
     for (mut visibility, reaction) in &mut reaction_query {
-        if state.is_in(&reaction.0) {
+        if old.is_some_and(|x| reaction.matches(x)) && !new.is_some_and(|x| reaction.matches(x)) {
             *visibility = Visibility::Hidden;
         }
     }
 }
 
-fn show_on_enter_state<S: State + Eq>(
+fn show_on_enter_state<S: State, R: StateReaction<S>>(
     state: NextRef<S>,
-    mut reaction_query: Query<(&mut Visibility, &VisibleInState<S>)>,
+    mut reaction_query: Query<(&mut Visibility, &R)>,
 ) {
     for (mut visibility, reaction) in &mut reaction_query {
-        if state.will_be_in(&reaction.0) {
+        if state.get().is_some_and(|x| reaction.matches(x)) {
             *visibility = Visibility::Inherited;
         }
     }
@@ -207,6 +279,8 @@ fn show_on_enable_state<S: State + Eq>(
 ///
 /// - On enter, the [`Disabled`] component will be removed recursively.
 /// - On exit, the [`Disabled`] component will be inserted recursively.
+///
+/// To enable an entity while in any value that matches a pattern, use [`EnabledInStatePattern`].
 #[derive(Component, Default)]
 #[cfg_attr(
     feature = "bevy_reflect",
@@ -218,13 +292,48 @@ pub struct EnabledInState<S: State>(
     pub S,
 );
 
-fn disable_on_exit_state<S: State + Eq>(
+impl<S: State + Eq> StateReaction<S> for EnabledInState<S> {
+    fn matches(&self, state: &S) -> bool {
+        &self.0 == state
+    }
+}
+
+/// A component that enables an entity (and its descendants) while in any value of the [`State`]
+/// type `S` that matches a pattern.
+///
+/// - On enter, the [`Disabled`] component will be removed recursively.
+/// - On exit, the [`Disabled`] component will be inserted recursively (unless the new state
+///   also matches).
+///
+/// This component can't be reflected, since the pattern is type-erased.
+#[derive(Component)]
+pub struct EnabledInStatePattern<S: State>(
+    /// The pattern of states during which the entity should be enabled.
+    pub BoxedStatePattern<S>,
+);
+
+impl<S: State> EnabledInStatePattern<S> {
+    /// Create an `EnabledInStatePattern` that's enabled while in a value that matches a pattern.
+    pub fn new(pattern: impl StatePattern<S>) -> Self {
+        Self(BoxedStatePattern::new(pattern))
+    }
+}
+
+impl<S: State> StateReaction<S> for EnabledInStatePattern<S> {
+    fn matches(&self, state: &S) -> bool {
+        self.0.matches(state)
+    }
+}
+
+fn disable_on_exit_state<S: State, R: StateReaction<S>>(
     mut commands: Commands,
-    state: CurrentRef<S>,
-    reaction_query: Query<(Entity, &EnabledInState<S>)>,
+    state: FlushRef<S>,
+    reaction_query: Query<(Entity, &R)>,
 ) {
+    // Stay enabled if the new state also matches.
+    let (old, new) = state.get();
     for (entity, reaction) in &reaction_query {
-        if state.is_in(&reaction.0) {
+        if old.is_some_and(|x| reaction.matches(x)) && !new.is_some_and(|x| reaction.matches(x)) {
             commands
                 .entity(entity)
                 .insert_recursive::<Children>(Disabled);
@@ -232,13 +341,14 @@ fn disable_on_exit_state<S: State + Eq>(
     }
 }
 
-fn enable_on_enter_state<S: State + Eq>(
+fn enable_on_enter_state<S: State, R: StateReaction<S>>(
     mut commands: Commands,
     state: NextRef<S>,
-    reaction_query: Query<(Entity, &EnabledInState<S>)>,
+    // Disabled entities are excluded from queries that don't mention `Disabled`.
+    reaction_query: Query<(Entity, &R), With<Disabled>>,
 ) {
     for (entity, reaction) in &reaction_query {
-        if state.will_be_in(&reaction.0) {
+        if state.get().is_some_and(|x| reaction.matches(x)) {
             commands
                 .entity(entity)
                 .remove_recursive::<Children, Disabled>();
@@ -271,7 +381,7 @@ fn disable_on_disable_state<S: State + Eq>(
 
 fn enable_on_enable_state<S: State + Eq>(
     mut commands: Commands,
-    reaction_query: Query<Entity, With<EnabledInEnabledState<S>>>,
+    reaction_query: Query<Entity, (With<EnabledInEnabledState<S>>, With<Disabled>)>,
 ) {
     for entity in &reaction_query {
         commands
@@ -0,0 +1,229 @@
+//! Load a [`NextStateSequence`] from a RON file as a [`NextStateSequenceAsset`].
+//!
+//! Enable the `sequence_asset` feature flag to use this module.
+//!
+//! A sequence file has the `.sequence.ron` extension and contains a list of states, where
+//! `None` is disabled:
+//!
+//! ```ron
+//! [Draw, Main, Combat, End, None]
+//! ```
+//!
+//! When the asset is loaded or hot reloaded, the [`NextStateSequence<S>`] resource is replaced
+//! and every [`NextStateIndex<S>`] is moved to point at the same logical entry in the new
+//! sequence (e.g. the second `Main` stays the second `Main`), or clamped within bounds if that
+//! entry was removed. Labels are also kept on the same logical entries.
+//!
+//! # Example
+//!
+//! ```
+//! # use bevy::{asset::AssetPlugin, prelude::*};
+//! # use pyri_state::{extra::sequence_asset::NextStateSequenceHandle, prelude::*};
+//! # use serde::Deserialize;
+//! #
+//! #[derive(State, Reflect, Deserialize, Clone, PartialEq, Eq)]
+//! #[state(next(NextStateIndex<Self>))]
+//! enum Phase {
+//!     Draw,
+//!     Main,
+//!     End,
+//! }
+//!
+//! fn load_phases(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     commands.insert_resource(NextStateSequenceHandle::<Phase>::new(
+//!         asset_server.load("phases.sequence.ron"),
+//!     ));
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins((MinimalPlugins, AssetPlugin::default(), StatePlugin))
+//!     .add_plugins(NextStateSequenceAssetPlugin::<Phase>::default())
+//!     .add_state::<Phase>()
+//!     .add_systems(Startup, load_phases);
+//! ```
+
+use alloc::{borrow::ToOwned as _, boxed::Box, vec::Vec};
+use core::{error::Error, marker::PhantomData};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_asset::{
+    Asset, AssetApp as _, AssetEvent, AssetLoader, Assets, Handle, LoadContext, io::Reader,
+};
+use bevy_ecs::{
+    change_detection::DetectChanges as _,
+    event::EventReader,
+    resource::Resource,
+    system::{Query, Res, ResMut},
+};
+use bevy_reflect::TypePath;
+use ron::{Options, extensions::Extensions};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    next_state::sequence::{NextStateIndex, NextStateSequence},
+    state::State,
+};
+
+/// A plugin that adds the [`NextStateSequenceAsset<S>`] asset type and its loader, and keeps
+/// the [`NextStateSequence<S>`] resource in
+/// sync with the asset in [`NextStateSequenceHandle<S>`].
+///
+/// Inserts an empty `NextStateSequence<S>` until the asset is loaded.
+///
+/// Requires `AssetPlugin`.
+pub struct NextStateSequenceAssetPlugin<S>(PhantomData<S>)
+where
+    S: State + Clone + PartialEq + TypePath + DeserializeOwned;
+
+impl<S> Plugin for NextStateSequenceAssetPlugin<S>
+where
+    S: State + Clone + PartialEq + TypePath + DeserializeOwned,
+{
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<NextStateSequence<S>>() {
+            app.insert_resource(NextStateSequence::<S>::new(Vec::new()));
+        }
+        app.init_asset::<NextStateSequenceAsset<S>>()
+            .register_asset_loader(NextStateSequenceLoader::<S>::default())
+            .add_systems(PreUpdate, sync_next_state_sequence::<S>);
+    }
+}
+
+impl<S> Default for NextStateSequenceAssetPlugin<S>
+where
+    S: State + Clone + PartialEq + TypePath + DeserializeOwned,
+{
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// An [`Asset`] that stores a sequence of next states for the [`State`] type `S`.
+///
+/// Loaded from a `.sequence.ron` file by [`NextStateSequenceLoader<S>`].
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct NextStateSequenceAsset<S: State + TypePath>(
+    /// The sequence of states.
+    pub Vec<Option<S>>,
+);
+
+/// A [`Resource`] that stores the handle of the [`NextStateSequenceAsset<S>`] to load into the
+/// [`NextStateSequence<S>`] resource.
+#[derive(Resource, Debug)]
+pub struct NextStateSequenceHandle<S: State + TypePath>(
+    /// The handle to the sequence asset.
+    pub Handle<NextStateSequenceAsset<S>>,
+);
+
+impl<S: State + TypePath> NextStateSequenceHandle<S> {
+    /// Create a new `NextStateSequenceHandle` from a handle.
+    pub fn new(handle: Handle<NextStateSequenceAsset<S>>) -> Self {
+        Self(handle)
+    }
+}
+
+/// An [`AssetLoader`] that deserializes a [`NextStateSequenceAsset<S>`] from a `.sequence.ron`
+/// file.
+///
+/// States can be written without `Some(...)`, so `[A, B, None]` is a valid sequence.
+#[derive(TypePath)]
+pub struct NextStateSequenceLoader<S>(PhantomData<S>);
+
+impl<S> Default for NextStateSequenceLoader<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: State + TypePath + DeserializeOwned> AssetLoader for NextStateSequenceLoader<S> {
+    type Asset = NextStateSequenceAsset<S>;
+    type Settings = ();
+    type Error = Box<dyn Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let sequence = Options::default()
+            .with_default_extension(Extensions::IMPLICIT_SOME)
+            .from_bytes(&bytes)?;
+        Ok(NextStateSequenceAsset(sequence))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["sequence.ron"]
+    }
+}
+
+/// Find the index of the same logical entry in a new sequence, or `None` if it was removed.
+///
+/// The same logical entry is the same occurrence of the same state (e.g. the second `Main`).
+fn find_entry<S: PartialEq>(old: &[Option<S>], new: &[Option<S>], index: usize) -> Option<usize> {
+    let value = old.get(index)?;
+    let nth = old[..index].iter().filter(|x| *x == value).count();
+    new.iter()
+        .enumerate()
+        .filter(|(_, x)| *x == value)
+        .nth(nth)
+        .map(|(i, _)| i)
+}
+
+fn sync_next_state_sequence<S: State + Clone + PartialEq + TypePath>(
+    mut events: EventReader<AssetEvent<NextStateSequenceAsset<S>>>,
+    assets: Res<Assets<NextStateSequenceAsset<S>>>,
+    handle: Option<Res<NextStateSequenceHandle<S>>>,
+    mut sequence: ResMut<NextStateSequence<S>>,
+    index: Option<ResMut<NextStateIndex<S>>>,
+    mut index_query: Query<&mut NextStateIndex<S>>,
+) {
+    let Some(handle) = handle else {
+        events.clear();
+        return;
+    };
+    let id = handle.0.id();
+    let changed = events
+        .read()
+        .filter(|event| {
+            matches!(
+                event,
+                AssetEvent::LoadedWithDependencies { id: x } | AssetEvent::Modified { id: x }
+                    if *x == id
+            )
+        })
+        .count()
+        > 0;
+    if !changed && !handle.is_changed() {
+        return;
+    }
+    let Some(asset) = assets.get(id) else {
+        return;
+    };
+
+    let (old, new) = (sequence.states(), &asset.0);
+    let remap = |index: &mut NextStateIndex<S>| {
+        if let Some(x) = index.0 {
+            let clamped = (!new.is_empty()).then(|| x.min(new.len() - 1));
+            index.0 = find_entry(old, new, x).or(clamped);
+        }
+    };
+    if let Some(mut index) = index {
+        remap(&mut index);
+    }
+    for mut index in &mut index_query {
+        remap(&mut index);
+    }
+
+    // Keep labels on the same logical entries.
+    let labels = sequence
+        .labels()
+        .filter_map(|(i, label)| Some((find_entry(old, new, i)?, label.to_owned())))
+        .collect::<Vec<_>>();
+    *sequence = NextStateSequence::new(new.clone());
+    for (i, label) in labels {
+        sequence.set_label(i, label);
+    }
+}
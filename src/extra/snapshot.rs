@@ -0,0 +1,741 @@
+//! Take a [`StateSnapshot`] of all registered states for save games.
+//!
+//! Enable the `snapshot` feature flag to use this module.
+//!
+//! A snapshot stores the current and next values of every [`State`] type added with
+//! [`AppExtState`](crate::setup::AppExtState) as reflected values, so they can be serialized
+//! with [`StateSnapshotSerializer`] and deserialized with [`StateSnapshotDeserializer`]. The next
+//! value includes the entire [`NextState`](crate::next_state::NextState) type (e.g. the contents
+//! of a [`NextStateStack`](crate::next_state::stack::NextStateStack)).
+//!
+//! A state type is only included if it's registered for reflection along with its
+//! `NextState` type, which [`SnapshotPlugin`] and [`LocalSnapshotPlugin`] take care of. Local
+//! states are only included for entities with a [`StateSnapshotId`].
+//!
+//! # Example
+//!
+//! Opt in to the [`SnapshotPlugin`] for `Screen` by adding `#[state(snapshot)]`:
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use pyri_state::{extra::snapshot::WorldExtStateSnapshot as _, prelude::*};
+//! #
+//! #[derive(State, Reflect, Clone, PartialEq, Eq, Default)]
+//! #[state(snapshot)]
+//! enum Screen {
+//!     #[default]
+//!     Title,
+//!     Gameplay,
+//! }
+//!
+//! fn save_game(world: &mut World) {
+//!     let snapshot = world.snapshot_states();
+//!     // ... serialize `snapshot` with `StateSnapshotSerializer` ...
+//! #   load_game(world, snapshot);
+//! }
+//!
+//! fn load_game(world: &mut World, snapshot: StateSnapshot) {
+//!     world.restore_states(&snapshot);
+//! }
+//! ```
+
+#[cfg(feature = "bevy_app")]
+pub use app::*;
+
+#[cfg(feature = "bevy_app")]
+mod app {
+    use core::marker::PhantomData;
+
+    use bevy_app::{App, Plugin};
+    use bevy_ecs::reflect::{AppTypeRegistry, ReflectComponent, ReflectResource};
+    use bevy_reflect::{FromReflect, GetTypeRegistration, TypePath};
+
+    use crate::state::{LocalState, State};
+
+    use super::StateSnapshotId;
+
+    /// A plugin that registers the [`State`] type `S` and its
+    /// [`NextState`](crate::next_state::NextState) type for reflection, so the global state is
+    /// included in a [`StateSnapshot`](super::StateSnapshot).
+    pub struct SnapshotPlugin<S: State + FromReflect + TypePath + GetTypeRegistration>(
+        PhantomData<S>,
+    )
+    where
+        S::Next: FromReflect + TypePath + GetTypeRegistration;
+
+    impl<S: State + FromReflect + TypePath + GetTypeRegistration> Plugin for SnapshotPlugin<S>
+    where
+        S::Next: FromReflect + TypePath + GetTypeRegistration,
+    {
+        fn build(&self, app: &mut App) {
+            let type_registry = app.world_mut().get_resource_or_init::<AppTypeRegistry>();
+            let mut type_registry = type_registry.write();
+            type_registry.register::<S>();
+            type_registry.register::<S::Next>();
+            type_registry.register_type_data::<S, ReflectResource>();
+            type_registry.register_type_data::<S::Next, ReflectResource>();
+        }
+    }
+
+    impl<S: State + FromReflect + TypePath + GetTypeRegistration> Default for SnapshotPlugin<S>
+    where
+        S::Next: FromReflect + TypePath + GetTypeRegistration,
+    {
+        fn default() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    /// A plugin that registers the local [`State`] type `S` and its
+    /// [`NextState`](crate::next_state::NextState) type for reflection, so local states on
+    /// entities with a [`StateSnapshotId`] are included in a
+    /// [`StateSnapshot`](super::StateSnapshot).
+    pub struct LocalSnapshotPlugin<S: LocalState + FromReflect + TypePath + GetTypeRegistration>(
+        PhantomData<S>,
+    )
+    where
+        S::Next: FromReflect + TypePath + GetTypeRegistration;
+
+    impl<S: LocalState + FromReflect + TypePath + GetTypeRegistration> Plugin for LocalSnapshotPlugin<S>
+    where
+        S::Next: FromReflect + TypePath + GetTypeRegistration,
+    {
+        fn build(&self, app: &mut App) {
+            let type_registry = app.world_mut().get_resource_or_init::<AppTypeRegistry>();
+            let mut type_registry = type_registry.write();
+            type_registry.register::<StateSnapshotId>();
+            type_registry.register::<S>();
+            type_registry.register::<S::Next>();
+            type_registry.register_type_data::<S, ReflectComponent>();
+            type_registry.register_type_data::<S::Next, ReflectComponent>();
+        }
+    }
+
+    impl<S: LocalState + FromReflect + TypePath + GetTypeRegistration> Default
+        for LocalSnapshotPlugin<S>
+    where
+        S::Next: FromReflect + TypePath + GetTypeRegistration,
+    {
+        fn default() -> Self {
+            Self(PhantomData)
+        }
+    }
+}
+
+#[cfg(feature = "debug")]
+use alloc::sync::Arc;
+use alloc::{
+    boxed::Box,
+    string::{String, ToString as _},
+    vec::Vec,
+};
+#[cfg(feature = "debug")]
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::{any::TypeId, fmt};
+
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::With,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    resource::Resource,
+    world::{EntityRef, EntityWorldMut, World},
+};
+use bevy_reflect::{
+    PartialReflect, Reflect, ReflectFromReflect, TypeRegistry,
+    serde::{ReflectDeserializer, ReflectSerializer},
+};
+use serde::{
+    Serialize, Serializer,
+    de::{
+        self, Deserialize, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor,
+    },
+    ser::{SerializeSeq as _, SerializeStruct as _},
+};
+
+use crate::{next_state::TriggerStateFlush, state::State};
+
+/// A snapshot of the current and next values of every registered [`State`] type.
+///
+/// Take a snapshot with [`WorldExtStateSnapshot::snapshot_states`] and restore it with
+/// [`WorldExtStateSnapshot::restore_states`].
+#[derive(Default, Debug)]
+pub struct StateSnapshot(
+    /// The snapshot of each registered state type.
+    pub Vec<StateTypeSnapshot>,
+);
+
+/// A snapshot of a single [`State`] type in a [`StateSnapshot`].
+#[derive(Debug)]
+pub struct StateTypeSnapshot {
+    /// The [type path](bevy_reflect::TypePath::type_path) of the state type.
+    pub type_path: String,
+    /// The current global state, or `None` if disabled.
+    ///
+    /// This is not restored by [`WorldExtStateSnapshot::restore_states`].
+    pub current: Option<Box<dyn PartialReflect>>,
+    /// The global [`NextState`](crate::next_state::NextState), or `None` if the global state
+    /// was not initialized.
+    pub next: Option<Box<dyn PartialReflect>>,
+    /// The local states on entities with a [`StateSnapshotId`].
+    pub local: Vec<LocalStateSnapshot>,
+}
+
+/// A snapshot of a local [`State`] on a single entity in a [`StateTypeSnapshot`].
+#[derive(Debug)]
+pub struct LocalStateSnapshot {
+    /// The [`StateSnapshotId`] of the entity with the local state.
+    pub id: String,
+    /// The current local state, or `None` if disabled.
+    ///
+    /// This is not restored by [`WorldExtStateSnapshot::restore_states`].
+    pub current: Option<Box<dyn PartialReflect>>,
+    /// The local [`NextState`](crate::next_state::NextState).
+    pub next: Box<dyn PartialReflect>,
+}
+
+/// A [`Component`] that identifies an entity for its local states in a [`StateSnapshot`].
+///
+/// Entity IDs aren't stable across save and load, so local states are only included in a
+/// snapshot for entities with this component, and restored on the entity with the same ID.
+#[derive(Component, Reflect, Clone, PartialEq, Eq, Hash, Debug)]
+#[reflect(Component)]
+pub struct StateSnapshotId(
+    /// The ID, which should be unique among entities with local states.
+    pub String,
+);
+
+/// An extension trait for [`World`] that provides methods for taking and restoring a
+/// [`StateSnapshot`].
+pub trait WorldExtStateSnapshot {
+    /// Take a snapshot of every registered state type.
+    ///
+    /// State types that aren't registered for reflection are skipped.
+    fn snapshot_states(&mut self) -> StateSnapshot;
+
+    /// Restore a snapshot of every registered state type.
+    ///
+    /// The current states are not restored directly. Instead, the next states are restored
+    /// and a flush is triggered, so the present states will exit and the restored states will
+    /// enter with the usual hooks in the [`StateFlush`](crate::schedule::StateFlush) schedule.
+    ///
+    /// Local states are only restored on entities with a matching [`StateSnapshotId`].
+    fn restore_states(&mut self, snapshot: &StateSnapshot);
+}
+
+impl WorldExtStateSnapshot for World {
+    fn snapshot_states(&mut self) -> StateSnapshot {
+        let Some(registry) = self.get_resource::<StateSnapshotRegistry>() else {
+            return StateSnapshot::default();
+        };
+
+        let entries = registry.0.clone();
+        let type_registry = self.get_resource_or_init::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let mut id_query = self.query_filtered::<EntityRef, With<StateSnapshotId>>();
+
+        let mut snapshot = StateSnapshot::default();
+        for types in entries.iter().filter_map(|x| x.reflect(&type_registry)) {
+            let local = types
+                .next_component
+                .map(|next| {
+                    id_query
+                        .iter(self)
+                        .filter_map(|entity| {
+                            Some(LocalStateSnapshot {
+                                next: next.reflect(entity)?.to_dynamic(),
+                                current: types
+                                    .state_component
+                                    .and_then(|x| x.reflect(entity))
+                                    .map(|x| x.to_dynamic()),
+                                id: entity.get::<StateSnapshotId>()?.0.clone(),
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            snapshot.0.push(StateTypeSnapshot {
+                type_path: types.type_path.to_string(),
+                current: types
+                    .state_resource
+                    .and_then(|x| x.reflect(&*self).ok())
+                    .map(|x| x.to_dynamic()),
+                next: types
+                    .next_resource
+                    .and_then(|x| x.reflect(&*self).ok())
+                    .map(|x| x.to_dynamic()),
+                local,
+            });
+        }
+
+        snapshot
+    }
+
+    fn restore_states(&mut self, snapshot: &StateSnapshot) {
+        let Some(registry) = self.get_resource::<StateSnapshotRegistry>() else {
+            return;
+        };
+
+        let entries = registry.0.clone();
+        let type_registry = self.get_resource_or_init::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let ids = self
+            .query::<(Entity, &StateSnapshotId)>()
+            .iter(self)
+            .map(|(entity, id)| (entity, id.0.clone()))
+            .collect::<Vec<_>>();
+
+        let entries = entries
+            .iter()
+            .filter_map(|entry| Some((entry, entry.reflect(&type_registry)?)))
+            .collect::<Vec<_>>();
+        for snapshot in &snapshot.0 {
+            let Some((entry, types)) = entries
+                .iter()
+                .find(|(_, types)| types.type_path == snapshot.type_path)
+            else {
+                continue;
+            };
+
+            if let (Some(next), Some(reflect_next)) = (
+                snapshot
+                    .next
+                    .as_deref()
+                    .and_then(|x| types.from_reflect.from_reflect(x)),
+                types.next_resource,
+            ) {
+                reflect_next.insert(self, next.as_partial_reflect(), &type_registry);
+                (entry.trigger)(self);
+            }
+
+            let Some(reflect_next) = types.next_component else {
+                continue;
+            };
+            for local in &snapshot.local {
+                let Some(&(entity, _)) = ids.iter().find(|(_, id)| *id == local.id) else {
+                    continue;
+                };
+                let Some(next) = types.from_reflect.from_reflect(local.next.as_ref()) else {
+                    continue;
+                };
+
+                let mut entity = self.entity_mut(entity);
+                reflect_next.insert(&mut entity, next.as_partial_reflect(), &type_registry);
+                (entry.trigger_local)(&mut entity);
+            }
+        }
+    }
+}
+
+/// The registered [`State`] types.
+#[derive(Resource, Default)]
+struct StateSnapshotRegistry(Vec<StateSnapshotEntry>);
+
+#[derive(Clone)]
+struct StateSnapshotEntry {
+    #[cfg(feature = "debug")]
+    name: &'static str,
+    /// Whether a warning was logged because the state type isn't registered for reflection.
+    ///
+    /// This is shared between clones so that the warning is only logged once.
+    #[cfg(feature = "debug")]
+    warned: Arc<AtomicBool>,
+    state: TypeId,
+    next: TypeId,
+    trigger: fn(&mut World),
+    trigger_local: fn(&mut EntityWorldMut),
+}
+
+/// The reflection data for a registered [`State`] type and its `NextState` type.
+struct ReflectStateTypes<'a> {
+    type_path: &'static str,
+    from_reflect: &'a ReflectFromReflect,
+    state_resource: Option<&'a ReflectResource>,
+    next_resource: Option<&'a ReflectResource>,
+    state_component: Option<&'a ReflectComponent>,
+    next_component: Option<&'a ReflectComponent>,
+}
+
+impl StateSnapshotEntry {
+    fn reflect<'a>(&self, type_registry: &'a TypeRegistry) -> Option<ReflectStateTypes<'a>> {
+        let (Some(state), Some(next)) =
+            (type_registry.get(self.state), type_registry.get(self.next))
+        else {
+            #[cfg(feature = "debug")]
+            if !self.warned.swap(true, Ordering::Relaxed) {
+                bevy_log::warn!(
+                    "Skipped {} in state snapshot because it isn't registered for reflection",
+                    self.name,
+                );
+            }
+            return None;
+        };
+
+        Some(ReflectStateTypes {
+            type_path: state.type_info().type_path(),
+            from_reflect: next.data::<ReflectFromReflect>()?,
+            state_resource: state.data::<ReflectResource>(),
+            next_resource: next.data::<ReflectResource>(),
+            state_component: state.data::<ReflectComponent>(),
+            next_component: next.data::<ReflectComponent>(),
+        })
+    }
+}
+
+fn trigger<S: State>(world: &mut World) {
+    world.get_resource_or_init::<TriggerStateFlush<S>>().0 = true;
+}
+
+fn trigger_local<S: State>(entity: &mut EntityWorldMut) {
+    let mut trigger = TriggerStateFlush::<S>::default();
+    trigger.0 = true;
+    entity.insert(trigger);
+}
+
+/// Register the [`State`] type `S` to be included in a [`StateSnapshot`] if it's registered for
+/// reflection.
+///
+/// Called by [`AppExtState`](crate::setup::AppExtState) when a state type is registered.
+pub fn register_snapshot<S: State>(world: &mut World) {
+    let mut registry = world.get_resource_or_init::<StateSnapshotRegistry>();
+    let state = TypeId::of::<S>();
+    if registry.0.iter().any(|x| x.state == state) {
+        return;
+    }
+
+    registry.0.push(StateSnapshotEntry {
+        #[cfg(feature = "debug")]
+        name: core::any::type_name::<S>(),
+        #[cfg(feature = "debug")]
+        warned: Arc::new(AtomicBool::new(false)),
+        state,
+        next: TypeId::of::<S::Next>(),
+        trigger: trigger::<S>,
+        trigger_local: trigger_local::<S>,
+    });
+}
+
+/// A [`Serialize`] implementation for a [`StateSnapshot`], using a [`TypeRegistry`] to
+/// serialize the reflected values.
+pub struct StateSnapshotSerializer<'a> {
+    snapshot: &'a StateSnapshot,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> StateSnapshotSerializer<'a> {
+    /// Create a new `StateSnapshotSerializer`.
+    pub fn new(snapshot: &'a StateSnapshot, registry: &'a TypeRegistry) -> Self {
+        Self { snapshot, registry }
+    }
+}
+
+impl Serialize for StateSnapshotSerializer<'_> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.snapshot.0.len()))?;
+        for snapshot in &self.snapshot.0 {
+            seq.serialize_element(&StateTypeSnapshotSerializer {
+                snapshot,
+                registry: self.registry,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+struct StateTypeSnapshotSerializer<'a> {
+    snapshot: &'a StateTypeSnapshot,
+    registry: &'a TypeRegistry,
+}
+
+impl Serialize for StateTypeSnapshotSerializer<'_> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let registry = self.registry;
+        let local = self
+            .snapshot
+            .local
+            .iter()
+            .map(|snapshot| LocalStateSnapshotSerializer { snapshot, registry })
+            .collect::<Vec<_>>();
+
+        let mut state = serializer.serialize_struct("StateTypeSnapshot", 4)?;
+        state.serialize_field("type_path", &self.snapshot.type_path)?;
+        state.serialize_field(
+            "current",
+            &reflect_serializer(&self.snapshot.current, registry),
+        )?;
+        state.serialize_field("next", &reflect_serializer(&self.snapshot.next, registry))?;
+        state.serialize_field("local", &local)?;
+        state.end()
+    }
+}
+
+struct LocalStateSnapshotSerializer<'a> {
+    snapshot: &'a LocalStateSnapshot,
+    registry: &'a TypeRegistry,
+}
+
+impl Serialize for LocalStateSnapshotSerializer<'_> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let registry = self.registry;
+        let mut state = serializer.serialize_struct("LocalStateSnapshot", 3)?;
+        state.serialize_field("id", &self.snapshot.id)?;
+        state.serialize_field(
+            "current",
+            &reflect_serializer(&self.snapshot.current, registry),
+        )?;
+        state.serialize_field(
+            "next",
+            &ReflectSerializer::new(self.snapshot.next.as_ref(), registry),
+        )?;
+        state.end()
+    }
+}
+
+fn reflect_serializer<'a>(
+    value: &'a Option<Box<dyn PartialReflect>>,
+    registry: &'a TypeRegistry,
+) -> Option<ReflectSerializer<'a>> {
+    value
+        .as_deref()
+        .map(|value| ReflectSerializer::new(value, registry))
+}
+
+/// A [`DeserializeSeed`] implementation for a [`StateSnapshot`], using a [`TypeRegistry`] to
+/// deserialize the reflected values.
+pub struct StateSnapshotDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> StateSnapshotDeserializer<'a> {
+    /// Create a new `StateSnapshotDeserializer`.
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for StateSnapshotDeserializer<'_> {
+    type Value = StateSnapshot;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for StateSnapshotDeserializer<'_> {
+    type Value = StateSnapshot;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a state snapshot")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut snapshot = StateSnapshot::default();
+        while let Some(x) = seq.next_element_seed(StateTypeSnapshotDeserializer(self.registry))? {
+            snapshot.0.push(x);
+        }
+        Ok(snapshot)
+    }
+}
+
+struct StateTypeSnapshotDeserializer<'a>(&'a TypeRegistry);
+
+impl<'de> DeserializeSeed<'de> for StateTypeSnapshotDeserializer<'_> {
+    type Value = StateTypeSnapshot;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        const FIELDS: &[&str] = &["type_path", "current", "next", "local"];
+        deserializer.deserialize_struct("StateTypeSnapshot", FIELDS, self)
+    }
+}
+
+impl<'de> Visitor<'de> for StateTypeSnapshotDeserializer<'_> {
+    type Value = StateTypeSnapshot;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a state type snapshot")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let registry = self.0;
+        Ok(StateTypeSnapshot {
+            type_path: seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?,
+            current: seq
+                .next_element_seed(OptionalReflectDeserializer(registry))?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?,
+            next: seq
+                .next_element_seed(OptionalReflectDeserializer(registry))?
+                .ok_or_else(|| de::Error::invalid_length(2, &self))?,
+            local: seq
+                .next_element_seed(LocalStateSnapshotsDeserializer(registry))?
+                .ok_or_else(|| de::Error::invalid_length(3, &self))?,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let registry = self.0;
+        let (mut type_path, mut current, mut next, mut local) = (None, None, None, None);
+        while let Some(FieldName(key)) = map.next_key()? {
+            match key.as_str() {
+                "type_path" => type_path = Some(map.next_value()?),
+                "current" => {
+                    current = Some(map.next_value_seed(OptionalReflectDeserializer(registry))?)
+                }
+                "next" => next = Some(map.next_value_seed(OptionalReflectDeserializer(registry))?),
+                "local" => {
+                    local = Some(map.next_value_seed(LocalStateSnapshotsDeserializer(registry))?)
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(StateTypeSnapshot {
+            type_path: type_path.ok_or_else(|| de::Error::missing_field("type_path"))?,
+            current: current.unwrap_or_default(),
+            next: next.unwrap_or_default(),
+            local: local.unwrap_or_default(),
+        })
+    }
+}
+
+struct LocalStateSnapshotsDeserializer<'a>(&'a TypeRegistry);
+
+impl<'de> DeserializeSeed<'de> for LocalStateSnapshotsDeserializer<'_> {
+    type Value = Vec<LocalStateSnapshot>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for LocalStateSnapshotsDeserializer<'_> {
+    type Value = Vec<LocalStateSnapshot>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of local state snapshots")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut local = Vec::new();
+        while let Some(x) = seq.next_element_seed(LocalStateSnapshotDeserializer(self.0))? {
+            local.push(x);
+        }
+        Ok(local)
+    }
+}
+
+struct LocalStateSnapshotDeserializer<'a>(&'a TypeRegistry);
+
+impl<'de> DeserializeSeed<'de> for LocalStateSnapshotDeserializer<'_> {
+    type Value = LocalStateSnapshot;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        const FIELDS: &[&str] = &["id", "current", "next"];
+        deserializer.deserialize_struct("LocalStateSnapshot", FIELDS, self)
+    }
+}
+
+impl<'de> Visitor<'de> for LocalStateSnapshotDeserializer<'_> {
+    type Value = LocalStateSnapshot;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a local state snapshot")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let registry = self.0;
+        Ok(LocalStateSnapshot {
+            id: seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?,
+            current: seq
+                .next_element_seed(OptionalReflectDeserializer(registry))?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?,
+            next: seq
+                .next_element_seed(ReflectDeserializer::new(registry))?
+                .ok_or_else(|| de::Error::invalid_length(2, &self))?,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let registry = self.0;
+        let (mut id, mut current, mut next) = (None, None, None);
+        while let Some(FieldName(key)) = map.next_key()? {
+            match key.as_str() {
+                "id" => id = Some(map.next_value()?),
+                "current" => {
+                    current = Some(map.next_value_seed(OptionalReflectDeserializer(registry))?)
+                }
+                "next" => next = Some(map.next_value_seed(ReflectDeserializer::new(registry))?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(LocalStateSnapshot {
+            id: id.ok_or_else(|| de::Error::missing_field("id"))?,
+            current: current.unwrap_or_default(),
+            next: next.ok_or_else(|| de::Error::missing_field("next"))?,
+        })
+    }
+}
+
+// A struct field name, deserialized as an identifier.
+struct FieldName(String);
+
+impl<'de> Deserialize<'de> for FieldName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_identifier(FieldNameVisitor)
+    }
+}
+
+struct FieldNameVisitor;
+
+impl Visitor<'_> for FieldNameVisitor {
+    type Value = FieldName;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a field name")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(FieldName(value.to_string()))
+    }
+}
+
+struct OptionalReflectDeserializer<'a>(&'a TypeRegistry);
+
+impl<'de> DeserializeSeed<'de> for OptionalReflectDeserializer<'_> {
+    type Value = Option<Box<dyn PartialReflect>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_option(self)
+    }
+}
+
+impl<'de> Visitor<'de> for OptionalReflectDeserializer<'_> {
+    type Value = Option<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an optional reflected value")
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        ReflectDeserializer::new(self.0)
+            .deserialize(deserializer)
+            .map(Some)
+    }
+}
@@ -122,14 +122,22 @@ pub mod state;
 /// ```
 pub mod prelude {
     pub use crate::{
-        access::{CurrentMut, CurrentRef, FlushMut, FlushRef, NextMut, NextRef},
-        next_state::{buffer::NextStateBuffer, stack::NextStateStackCommandsExt as _},
+        access::{CurrentMut, CurrentRef, FlushMut, FlushRef, LocalFlushRef, NextMut, NextRef},
+        next_state::buffer::NextStateBuffer,
         pattern::{
             StatePattern as _, StatePatternExtClone as _, StatePatternExtEq as _,
-            StateTransPattern as _, StateTransPatternExtClone as _,
+            StatePatternExtLocal as _, StatePatternTuple as _, StateTransPattern as _,
+            StateTransPatternExtClone as _, StateTransPatternExtLocal as _,
         },
-        schedule::{StateFlush, flush_event::StateFlushEvent},
-        setup::{CommandsExtState as _, EntityCommandsExtState as _},
+        schedule::{
+            FixedStateFlush, StateFlush,
+            computed_state::ComputedState,
+            flush_event::StateFlushEvent,
+            flush_observer::{OnStateEnter, OnStateExit, OnStateTrans},
+            resolve_state::StateFlushRejected,
+            transition_table::TransitionTable,
+        },
+        setup::{CommandsExtState as _, EntityCommandsExtState as _, WorldExtState as _},
         state,
         state::{
             State, StateExtEq as _, StateMut as _, StateMutExtClone as _, StateMutExtDefault as _,
@@ -137,31 +145,50 @@ pub mod prelude {
     };
 
     #[cfg(feature = "bevy_app")]
-    pub use crate::setup::{AppExtState as _, StatePlugin};
+    pub use crate::setup::{AppExtState as _, StateFlushInPlugin, StatePlugin, StateSettlePlugin};
 
     #[cfg(feature = "bevy_state")]
     pub use crate::extra::bevy_state::{BevyState, StateExtBevy as _};
 
     #[cfg(feature = "debug")]
-    pub use crate::debug::StateDebugSettings;
+    pub use crate::debug::{StateDebugSettings, registry::StateRegistry};
+
+    #[cfg(feature = "delay")]
+    pub use crate::extra::delay::{
+        DelayedStateFlush, StateMutExtDelay as _, StateMutExtDelayClone as _,
+    };
+
+    #[cfg(feature = "history")]
+    pub use crate::extra::history::{StateHistory, StateMutExtHistory as _};
 
     #[cfg(feature = "react")]
     pub use crate::extra::react::{
         DespawnOnDisableState, DespawnOnExitState, EnabledInEnabledState, EnabledInState,
-        VisibleInEnabledState, VisibleInState,
+        EnabledInStatePattern, VisibleInEnabledState, VisibleInState, VisibleInStatePattern,
     };
 
+    #[cfg(feature = "requests")]
+    pub use crate::next_state::requests::{NextStateRequests, NextStateRequestsCommandsExt as _};
+
     #[cfg(feature = "sequence")]
     pub use crate::next_state::sequence::{
-        NextStateIndex, NextStateIndexMut as _, NextStateSequence,
+        NextStateIndex, NextStateIndexCommandsExt as _, NextStateIndexMut as _,
+        NextStateOwnedSequence, NextStateOwnedSequenceCommandsExt as _, NextStateSequence,
     };
 
+    #[cfg(feature = "sequence_asset")]
+    pub use crate::extra::sequence_asset::NextStateSequenceAssetPlugin;
+
+    #[cfg(feature = "snapshot")]
+    pub use crate::extra::snapshot::{StateSnapshot, StateSnapshotId, WorldExtStateSnapshot as _};
+
     #[cfg(feature = "split")]
     pub use crate::{add_to_split_state, extra::split::SplitState};
 
     #[cfg(feature = "stack")]
     pub use crate::next_state::stack::{
-        NextStateStack, NextStateStackMut as _, NextStateStackMutExtClone as _,
+        NextStateStack, NextStateStackCommandsExt as _, NextStateStackMut as _,
+        NextStateStackMutExtClone as _,
     };
 
     /// A derive macro for the [`State`],
@@ -202,7 +229,7 @@ pub mod prelude {
     /// # #[state(no_defaults)]
     /// # struct RawState;
     /// #
-    /// #[derive(State, Component, Clone, PartialEq, Eq, Hash, Debug)]
+    /// #[derive(State, Component, Reflect, Clone, PartialEq, Eq, Hash, Debug, Default)]
     /// #[state(
     ///     // Disable default plugins: detect_change, flush_event, apply_flush.
     ///     no_defaults,
@@ -212,22 +239,63 @@ pub mod prelude {
     ///     detect_change,
     ///     // Send an event on flush (requires Clone).
     ///     flush_event,
+    ///     // Trigger observers on exit, transition, and enter (requires Clone).
+    ///     flush_observer,
     ///     // Log on flush (requires Debug).
     ///     log_flush,
+    ///     // Detect flush loops (requires Clone, PartialEq, Debug).
+    ///     detect_loop,
     ///     // Include a `BevyState<Self>` wrapper (requires StateMut, Clone, PartialEq, Eq, Hash, Debug).
     ///     bevy_state,
     ///     // Enable reaction components such as `DespawnOnExitState<Self>` (requires Eq).
     ///     react,
+    ///     // Enable delayed flushes with `DelayedStateFlush<Self>` (requires StateMut).
+    ///     delay,
+    ///     // Record the last N flushed values in `StateHistory<Self>` (requires Clone, PartialEq).
+    ///     history = 10,
+    ///     // Merge prioritized next state requests with `NextStateRequests<Self>` (requires PartialEq, Debug).
+    ///     requests,
+    ///     // Register this state for reflection so it's included in `StateSnapshot` (requires Reflect).
+    ///     snapshot,
     ///     // Clone the next state into the current state on flush (requires Clone).
     ///     apply_flush,
     ///     // Swap out the default `NextStateBuffer<Self>` for another `NextState` type.
     ///     next(NextStateStack<Self>),
+    ///     // Only run exit / enter hooks for patterns that don't match both the old and new states.
+    ///     hierarchical,
     ///     // Run this state's on-flush hooks after the listed states.
     ///     after(MyState),
     ///     // Run this state's on-flush hooks before the listed states.
     ///     before(RawState),
+    ///     // Only flush this state in the listed flush schedules (defaults to all).
+    ///     flush_in(StateFlush),
+    ///     // Enable this state while a parent state matches a pattern (requires StateMut, Clone, Default).
+    ///     sub_of = MyState,
+    ///     // The pattern for `sub_of` (defaults to `MyState::ANY`).
+    ///     when = MyState::ANY,
+    ///     // Re-enable this state with its last value instead of its default value.
+    ///     remember,
+    ///     // Reject any transition that isn't listed (requires StateMut, Clone). Rejections are
+    ///     // only logged with `log_flush` and `StateDebugSettings::log_reject`.
+    ///     transitions(Self => Self),
     /// )]
     /// struct ConfiguredState;
+    ///
+    /// #[derive(State, Clone, PartialEq, Eq)]
+    /// #[state(
+    ///     // Compute the next state from its sources (requires ComputedState, can't be combined
+    ///     // with `sub_of`).
+    ///     computed,
+    /// )]
+    /// struct DerivedState;
+    /// #
+    /// # impl ComputedState for DerivedState {
+    /// #     type Sources = MyState;
+    /// #
+    /// #     fn compute(_: Option<&MyState>) -> Option<Self> {
+    /// #         None
+    /// #     }
+    /// # }
     /// ```
     pub use pyri_state_derive::State;
 }
@@ -5,6 +5,8 @@
 //! - [`NextStateBuffer`](buffer::NextStateBuffer) (default)
 //! - [`NextStateStack`](stack::NextStateStack)
 //! - [`NextStateIndex`](sequence::NextStateIndex)
+//! - [`NextStateOwnedSequence`](sequence::NextStateOwnedSequence)
+//! - [`NextStateRequests`](requests::NextStateRequests)
 
 use core::marker::PhantomData;
 
@@ -12,6 +14,7 @@ use core::marker::PhantomData;
 use bevy_ecs::reflect::ReflectResource;
 use bevy_ecs::{
     component::Component,
+    entity::Entity,
     resource::Resource,
     system::{ReadOnlySystemParam, SystemParam, SystemParamItem},
 };
@@ -19,6 +22,8 @@ use bevy_ecs::{
 use crate::state::State;
 
 pub mod buffer;
+#[cfg(feature = "requests")]
+pub mod requests;
 #[cfg(feature = "sequence")]
 pub mod sequence;
 #[cfg(feature = "stack")]
@@ -44,6 +49,22 @@ impl<S: State> Default for TriggerStateFlush<S> {
     }
 }
 
+/// Warn that a command was skipped because `T` doesn't exist as a resource (or as a component
+/// on the entity for local state).
+///
+/// Commands on states that haven't been added do nothing instead of panicking.
+#[cfg_attr(not(feature = "debug"), allow(unused_variables))]
+pub(crate) fn warn_missing<T>(entity: Option<Entity>) {
+    #[cfg(feature = "debug")]
+    {
+        let ty = core::any::type_name::<T>();
+        match entity {
+            Some(entity) => bevy_log::warn!("Skipped state command because {entity} has no {ty}"),
+            None => bevy_log::warn!("Skipped state command because {ty} doesn't exist"),
+        }
+    }
+}
+
 /// A [`Resource`] that determines the next state for [`Self::State`].
 ///
 /// Use [`NextRef`](crate::access::NextRef) or [`FlushRef`](crate::access::FlushRef)
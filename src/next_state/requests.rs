@@ -0,0 +1,404 @@
+//! Store the [`NextState`] as a [`NextStateRequests`] that merges prioritized requests.
+//!
+//! Enable the `requests` feature flag to use this module.
+//!
+//! When multiple systems set the next state in the same frame, the last writer wins. With
+//! `NextStateRequests`, each system submits a request with a priority instead, and the highest
+//! priority request wins during [`ResolveStateSet::Compute`]. Resolving a request also triggers
+//! a flush, so there's no need to call `trigger` after submitting one. If the `debug` feature is
+//! enabled, conflicting requests will be logged as a warning.
+//!
+//! The next state can still be set directly through [`NextMut`](crate::access::NextMut), but
+//! pending requests will override it when they're resolved.
+//!
+//! # Example
+//!
+//! Opt in to `NextStateRequests` and the [`NextStateRequestsPlugin`] for `Screen` by adding
+//! `#[state(requests)]`:
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use pyri_state::prelude::*;
+//! #
+//! #[derive(State, Clone, PartialEq, Eq, Debug)]
+//! #[state(requests)]
+//! enum Screen {
+//!     Title,
+//!     Gameplay,
+//!     GameOver,
+//! }
+//!
+//! fn check_player_death(mut next: ResMut<NextStateRequests<Screen>>) {
+//!     next.request_enter(10, Screen::GameOver);
+//! }
+//!
+//! fn check_level_finished(mut next: ResMut<NextStateRequests<Screen>>) {
+//!     next.request_enter(0, Screen::Title);
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(StatePlugin)
+//!     .add_state::<Screen>()
+//!     .add_systems(Update, (check_player_death, check_level_finished));
+//! ```
+
+#[cfg(feature = "bevy_app")]
+pub use app::*;
+
+#[cfg(feature = "bevy_app")]
+mod app {
+    use core::{fmt::Debug, marker::PhantomData};
+
+    use bevy_app::{App, Plugin};
+
+    use crate::{
+        schedule::StateFlush,
+        state::{LocalState, State},
+    };
+
+    use super::{schedule_local_next_state_requests, schedule_next_state_requests};
+
+    /// A plugin that adds a system to resolve the
+    /// [`NextStateRequests<S>`](super::NextStateRequests) for the [`State`] type `S` to the
+    /// [`StateFlush`] schedule.
+    ///
+    /// Calls [`schedule_next_state_requests<S>`].
+    pub struct NextStateRequestsPlugin<S: State + PartialEq + Debug>(PhantomData<S>);
+
+    impl<S: State + PartialEq + Debug> Plugin for NextStateRequestsPlugin<S> {
+        fn build(&self, app: &mut App) {
+            schedule_next_state_requests::<S>(app.get_schedule_mut(StateFlush).unwrap());
+        }
+    }
+
+    impl<S: State + PartialEq + Debug> Default for NextStateRequestsPlugin<S> {
+        fn default() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    /// A plugin that adds a system to resolve the local
+    /// [`NextStateRequests<S>`](super::NextStateRequests) for the [`State`] type `S` to the
+    /// [`StateFlush`] schedule.
+    ///
+    /// Calls [`schedule_local_next_state_requests<S>`].
+    pub struct LocalNextStateRequestsPlugin<S: LocalState + PartialEq + Debug>(PhantomData<S>);
+
+    impl<S: LocalState + PartialEq + Debug> Plugin for LocalNextStateRequestsPlugin<S> {
+        fn build(&self, app: &mut App) {
+            schedule_local_next_state_requests::<S>(app.get_schedule_mut(StateFlush).unwrap());
+        }
+    }
+
+    impl<S: LocalState + PartialEq + Debug> Default for LocalNextStateRequestsPlugin<S> {
+        fn default() -> Self {
+            Self(PhantomData)
+        }
+    }
+}
+
+use alloc::vec::Vec;
+#[cfg(feature = "debug")]
+use core::any::type_name;
+use core::fmt::Debug;
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_ecs::reflect::ReflectResource;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    resource::Resource,
+    schedule::{IntoScheduleConfigs as _, Schedule},
+    system::{Commands, EntityCommands, Query, ResMut, SystemParamItem},
+    world::{EntityWorldMut, FromWorld, World},
+};
+#[cfg(feature = "debug")]
+use bevy_log::warn;
+
+use crate::{
+    next_state::{NextState, NextStateMut, TriggerStateFlush, warn_missing},
+    schedule::ResolveStateSet,
+    state::{LocalState, State},
+};
+
+/// A [`NextState`] type that stores the [`State`] type `S` as the winner of prioritized
+/// requests.
+///
+/// Requests are resolved in [`ResolveStateSet::Compute`] by [`NextStateRequestsPlugin<S>`]:
+/// the request with the highest priority wins, and the earliest request wins a tie. Resolving a
+/// request triggers a flush. If there are no requests, the next state is left unchanged.
+///
+/// Setting the next state directly via [`NextStateMut`] bypasses the requests, but doesn't
+/// remove them.
+#[derive(Resource, Component, Debug)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Resource)
+)]
+pub struct NextStateRequests<S: State> {
+    /// The resolved next state, or `None` if disabled.
+    next: Option<S>,
+    /// The pending requests as `(priority, state)` pairs in order of submission.
+    requests: Vec<(i32, Option<S>)>,
+}
+
+impl<S: State> NextState for NextStateRequests<S> {
+    type State = S;
+
+    type Param = ();
+
+    fn empty() -> Self {
+        Self::disabled()
+    }
+
+    fn next_state<'s>(
+        &'s self,
+        _param: &'s SystemParamItem<Self::Param>,
+    ) -> Option<&'s Self::State> {
+        self.get()
+    }
+}
+
+impl<S: State> NextStateMut for NextStateRequests<S> {
+    type ParamMut = ();
+
+    fn next_state_from_mut<'s>(
+        &'s self,
+        _param: &'s SystemParamItem<Self::ParamMut>,
+    ) -> Option<&'s Self::State> {
+        self.get()
+    }
+
+    fn next_state_mut<'s>(
+        &'s mut self,
+        _param: &'s mut SystemParamItem<Self::ParamMut>,
+    ) -> Option<&'s mut Self::State> {
+        self.next.as_mut()
+    }
+
+    fn set_next_state(
+        &mut self,
+        _param: &mut SystemParamItem<Self::ParamMut>,
+        state: Option<Self::State>,
+    ) {
+        self.next = state;
+    }
+}
+
+impl<S: State + FromWorld> FromWorld for NextStateRequests<S> {
+    fn from_world(world: &mut World) -> Self {
+        Self::enabled(S::from_world(world))
+    }
+}
+
+impl<S: State> NextStateRequests<S> {
+    /// Create a disabled `NextStateRequests` with no pending requests.
+    pub fn disabled() -> Self {
+        Self {
+            next: None,
+            requests: Vec::new(),
+        }
+    }
+
+    /// Create an enabled `NextStateRequests` with a specific value and no pending requests.
+    pub fn enabled(state: S) -> Self {
+        Self {
+            next: Some(state),
+            requests: Vec::new(),
+        }
+    }
+
+    /// Get a reference to the resolved next state, or `None` if disabled.
+    pub fn get(&self) -> Option<&S> {
+        self.next.as_ref()
+    }
+
+    /// Get the pending requests as `(priority, state)` pairs in order of submission.
+    pub fn requests(&self) -> &[(i32, Option<S>)] {
+        &self.requests
+    }
+
+    /// Request to set the next state to a new value, or `None` to disable.
+    pub fn request(&mut self, priority: i32, state: Option<S>) {
+        self.requests.push((priority, state));
+    }
+
+    /// Request to enable the next state with a specific value.
+    pub fn request_enter(&mut self, priority: i32, state: S) {
+        self.request(priority, Some(state));
+    }
+
+    /// Request to disable the next state.
+    pub fn request_disable(&mut self, priority: i32) {
+        self.request(priority, None);
+    }
+
+    /// Remove all pending requests.
+    pub fn clear_requests(&mut self) {
+        self.requests.clear();
+    }
+
+    /// Set the next state to the highest priority request and remove all pending requests.
+    ///
+    /// Returns false and leaves the next state unchanged if there are no requests.
+    pub fn resolve(&mut self) -> bool {
+        // `max_by_key` returns the last maximum, so search in reverse to prefer the earliest.
+        let Some(index) = self
+            .requests
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, (priority, _))| *priority)
+            .map(|(i, _)| i)
+        else {
+            return false;
+        };
+
+        self.next = self.requests.swap_remove(index).1;
+        self.requests.clear();
+        true
+    }
+}
+
+impl<S: State + PartialEq> NextStateRequests<S> {
+    /// Check if the pending requests disagree on the next state.
+    pub fn has_conflict(&self) -> bool {
+        self.requests
+            .split_first()
+            .is_some_and(|((_, first), rest)| rest.iter().any(|(_, x)| x != first))
+    }
+}
+
+/// Warn about conflicting requests before they're resolved.
+#[cfg(feature = "debug")]
+fn warn_conflict<S: State + PartialEq + Debug>(
+    next: &NextStateRequests<S>,
+    entity: Option<Entity>,
+) {
+    if !next.has_conflict() {
+        return;
+    }
+
+    let ty = type_name::<S>();
+    let requests = next.requests();
+    match entity {
+        Some(entity) => warn!("{ty} ({entity}) has conflicting next state requests: {requests:?}"),
+        None => warn!("{ty} has conflicting next state requests: {requests:?}"),
+    }
+}
+
+#[cfg(not(feature = "debug"))]
+fn warn_conflict<S: State>(_next: &NextStateRequests<S>, _entity: Option<Entity>) {}
+
+fn resolve_next_state_requests<S: State + PartialEq + Debug>(
+    mut next: ResMut<NextStateRequests<S>>,
+    mut trigger: ResMut<TriggerStateFlush<S>>,
+) {
+    if next.requests.is_empty() {
+        return;
+    }
+
+    warn_conflict(&next, None);
+    next.resolve();
+    trigger.0 = true;
+}
+
+/// Add a system to resolve the [`NextStateRequests<S>`] for the [`State`] type `S` to a
+/// schedule.
+///
+/// Used in [`NextStateRequestsPlugin<S>`].
+pub fn schedule_next_state_requests<S: State + PartialEq + Debug>(schedule: &mut Schedule) {
+    schedule.add_systems(resolve_next_state_requests::<S>.in_set(ResolveStateSet::<S>::Compute));
+}
+
+fn resolve_local_next_state_requests<S: LocalState + PartialEq + Debug>(
+    mut next_query: Query<(Entity, &mut NextStateRequests<S>, &mut TriggerStateFlush<S>)>,
+) {
+    for (entity, mut next, mut trigger) in &mut next_query {
+        if next.requests.is_empty() {
+            continue;
+        }
+
+        warn_conflict(&next, Some(entity));
+        next.resolve();
+        trigger.0 = true;
+    }
+}
+
+/// Add a system to resolve the local [`NextStateRequests<S>`] for the [`State`] type `S` to a
+/// schedule.
+///
+/// Used in [`LocalNextStateRequestsPlugin<S>`].
+pub fn schedule_local_next_state_requests<S: LocalState + PartialEq + Debug>(
+    schedule: &mut Schedule,
+) {
+    schedule
+        .add_systems(resolve_local_next_state_requests::<S>.in_set(ResolveStateSet::<S>::Compute));
+}
+
+/// An extension trait for [`Commands`] and [`EntityCommands`] that provides methods for
+/// operating on states with [`NextStateRequests`] as their `Next` type.
+///
+/// With `EntityCommands`, the methods operate on the local state of the entity instead.
+pub trait NextStateRequestsCommandsExt {
+    /// Queue a command to request to set the next state to a new value, or `None` to disable.
+    ///
+    /// The request will trigger a flush once it's resolved.
+    fn state_request<S: State<Next = NextStateRequests<S>>>(
+        &mut self,
+        priority: i32,
+        state: Option<S>,
+    ) -> &mut Self;
+
+    /// Queue a command to request to enable the next state with a specific value.
+    fn state_request_enter<S: State<Next = NextStateRequests<S>>>(
+        &mut self,
+        priority: i32,
+        state: S,
+    ) -> &mut Self {
+        self.state_request(priority, Some(state))
+    }
+
+    /// Queue a command to request to disable the next state.
+    fn state_request_disable<S: State<Next = NextStateRequests<S>>>(
+        &mut self,
+        priority: i32,
+    ) -> &mut Self {
+        self.state_request::<S>(priority, None)
+    }
+}
+
+impl NextStateRequestsCommandsExt for Commands<'_, '_> {
+    fn state_request<S: State<Next = NextStateRequests<S>>>(
+        &mut self,
+        priority: i32,
+        state: Option<S>,
+    ) -> &mut Self {
+        self.queue(move |world: &mut World| {
+            if let Some(mut next) = world.get_resource_mut::<NextStateRequests<S>>() {
+                next.request(priority, state);
+            } else {
+                warn_missing::<NextStateRequests<S>>(None);
+            }
+        });
+        self
+    }
+}
+
+impl NextStateRequestsCommandsExt for EntityCommands<'_> {
+    fn state_request<S: State<Next = NextStateRequests<S>>>(
+        &mut self,
+        priority: i32,
+        state: Option<S>,
+    ) -> &mut Self {
+        self.queue(move |mut entity: EntityWorldMut| {
+            if let Some(mut next) = entity.get_mut::<NextStateRequests<S>>() {
+                next.request(priority, state);
+            } else {
+                warn_missing::<NextStateRequests<S>>(Some(entity.id()));
+            }
+        });
+        self
+    }
+}
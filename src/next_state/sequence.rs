@@ -3,38 +3,191 @@
 //! Enable the `sequence` feature flag to use this module.
 //!
 //! This can be used to implement phases in a turn-based game, for example.
+//!
+//! The `NextStateSequence` is a global resource shared by every `NextStateIndex`. Use
+//! [`NextStateOwnedSequence`] instead to give each entity with a local state its own sequence.
+//!
+//! Entries in the sequence can be labeled, so an index can seek to a label (e.g. `"draw"`)
+//! instead of a numeric position that would break whenever an entry is inserted.
+//!
+//! # Example
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use pyri_state::prelude::*;
+//! #
+//! #[derive(State, Clone, PartialEq, Eq, Debug)]
+//! #[state(next(NextStateIndex<Self>))]
+//! enum Phase {
+//!     Upkeep,
+//!     Draw,
+//!     Main,
+//!     End,
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(StatePlugin)
+//!     .insert_resource(
+//!         NextStateSequence::new([
+//!             Some(Phase::Upkeep),
+//!             Some(Phase::Draw),
+//!             Some(Phase::Main),
+//!             Some(Phase::End),
+//!         ])
+//!         .with_label(1, "draw"),
+//!     )
+//!     .add_state::<Phase>()
+//!     .add_systems(Update, (
+//!         Phase::seek_label("draw").run_if(Phase::End.will_update()),
+//!         Phase::ping_pong_next.run_if(Phase::Main.will_update()),
+//!     ));
+//! ```
 
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 use core::marker::PhantomData;
 
 #[cfg(feature = "bevy_reflect")]
 use bevy_ecs::reflect::ReflectResource;
 use bevy_ecs::{
+    change_detection::Mut,
     component::Component,
     resource::Resource,
-    system::{Res, ResMut, SystemParamItem, lifetimeless::SRes},
+    system::{Commands, EntityCommands, Res, ResMut, SystemParamItem, lifetimeless::SRes},
+    world::{EntityWorldMut, World},
 };
 
-use crate::{next_state::NextState, state::State};
+use crate::{
+    next_state::{NextState, warn_missing},
+    pattern::StatePattern,
+    state::State,
+};
 
 /// A [`Resource`] that stores a sequence of next states for the [`State`] type `S`.
 ///
-/// Indexed into by the [`NextState`] type [`NextStateIndex<S>`].
+/// Indexed into by the [`NextState`] type [`NextStateIndex<S>`]. Each entry can have an
+/// optional label, which stays attached to its entry when entries are inserted or removed.
 #[derive(Resource, Debug)]
 #[cfg_attr(
     feature = "bevy_reflect",
     derive(bevy_reflect::Reflect),
     reflect(Resource)
 )]
-pub struct NextStateSequence<S: State>(
-    /// The sequence of states.
-    pub Vec<Option<S>>,
-);
+pub struct NextStateSequence<S: State> {
+    states: Vec<Option<S>>,
+    labels: Vec<Option<String>>,
+}
 
 impl<S: State> NextStateSequence<S> {
     /// Create a new `NextStateSequence` from a sequence of `Option<S>`.
     pub fn new(sequence: impl Into<Vec<Option<S>>>) -> Self {
-        Self(sequence.into())
+        let states = sequence.into();
+        let labels = alloc::vec![None; states.len()];
+        Self { states, labels }
+    }
+
+    /// Get the sequence of states.
+    pub fn states(&self) -> &[Option<S>] {
+        &self.states
+    }
+
+    /// Get the sequence of states as a mutable slice.
+    pub fn states_mut(&mut self) -> &mut [Option<S>] {
+        &mut self.states
+    }
+
+    /// Get the number of entries in the sequence.
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Check if the sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Get the state at an index, or `None` if disabled or out of bounds.
+    pub fn get(&self, index: usize) -> Option<&S> {
+        self.states.get(index)?.as_ref()
+    }
+
+    /// Append an unlabeled entry to the end of the sequence.
+    pub fn push(&mut self, state: Option<S>) {
+        self.states.push(state);
+        self.labels.push(None);
+    }
+
+    /// Insert an unlabeled entry at an index, shifting the following entries and their labels.
+    ///
+    /// Panics if `index` is greater than the length of the sequence.
+    pub fn insert(&mut self, index: usize, state: Option<S>) {
+        self.states.insert(index, state);
+        self.labels.insert(index, None);
+    }
+
+    /// Remove the entry at an index along with its label, shifting the following entries and
+    /// their labels.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Option<S> {
+        self.labels.remove(index);
+        self.states.remove(index)
+    }
+
+    /// Label the entry at an index.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn with_label(mut self, index: usize, label: impl Into<String>) -> Self {
+        self.set_label(index, label);
+        self
+    }
+
+    /// Label the entry at an index, replacing its previous label.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_label(&mut self, index: usize, label: impl Into<String>) {
+        self.labels[index] = Some(label.into());
+    }
+
+    /// Remove the label from the entry at an index.
+    pub fn remove_label(&mut self, index: usize) {
+        if let Some(label) = self.labels.get_mut(index) {
+            *label = None;
+        }
+    }
+
+    /// Get the label of the entry at an index, or `None` if there is no label.
+    pub fn label(&self, index: usize) -> Option<&str> {
+        self.labels.get(index)?.as_deref()
+    }
+
+    /// Find the index of the first entry with a label, or `None` if there is no such entry.
+    pub fn find_label(&self, label: &str) -> Option<usize> {
+        self.labels.iter().position(|x| x.as_deref() == Some(label))
+    }
+
+    /// Iterate over the labeled entries as `(index, label)` pairs.
+    pub fn labels(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.labels
+            .iter()
+            .enumerate()
+            .filter_map(|(i, x)| Some((i, x.as_deref()?)))
+    }
+
+    /// Find the index of the first entry after `index` with a state that matches a pattern.
+    ///
+    /// Disabled entries never match. Wraps around to the start of the sequence if `wrap` is true.
+    pub fn find_matching(
+        &self,
+        index: Option<usize>,
+        pattern: &impl StatePattern<S>,
+        wrap: bool,
+    ) -> Option<usize> {
+        let start = index.map_or(0, |x| x + 1);
+        let len = self.states.len();
+        let end = if wrap { start + len } else { len.max(start) };
+        (start..end)
+            .map(|i| i % len)
+            .find(|&i| matches!(&self.states[i], Some(x) if pattern.matches(x)))
     }
 }
 
@@ -42,6 +195,12 @@ impl<S: State> NextStateSequence<S> {
 /// an external [`NextStateSequence<S>`] resource.
 ///
 /// Using this as [`State::Next`] unlocks the [`NextStateIndexMut`] extension trait for `S`.
+///
+/// The index can be moved in three modes:
+///
+/// - Clamped (e.g. [`Self::next`]): Stop at the ends of the sequence.
+/// - Wrapping (e.g. [`Self::wrapping_next`]): Loop back around to the other end.
+/// - Ping-pong (e.g. [`Self::ping_pong_next`]): Bounce off the ends and reverse direction.
 #[derive(Resource, Component, Debug)]
 #[cfg_attr(
     feature = "bevy_reflect",
@@ -52,6 +211,8 @@ pub struct NextStateIndex<S: State>(
     /// The index into the sequence, or `None` if not in the sequence.
     pub Option<usize>,
     PhantomData<S>,
+    /// Whether ping-pong stepping is moving backwards.
+    bool,
 );
 
 impl<S: State> NextState for NextStateIndex<S> {
@@ -60,22 +221,20 @@ impl<S: State> NextState for NextStateIndex<S> {
     type Param = SRes<NextStateSequence<Self::State>>;
 
     fn empty() -> Self {
-        Self(None, PhantomData)
+        Self(None, PhantomData, false)
     }
 
     fn next_state<'s>(
         &'s self,
         param: &'s SystemParamItem<Self::Param>,
     ) -> Option<&'s Self::State> {
-        self.0
-            .and_then(|index| param.0.get(index))
-            .and_then(Option::as_ref)
+        self.get(param)
     }
 }
 
 impl<S: State> Default for NextStateIndex<S> {
     fn default() -> Self {
-        Self(Some(0), PhantomData)
+        Self(Some(0), PhantomData, false)
     }
 }
 
@@ -87,6 +246,11 @@ impl<S: State> NextStateIndex<S> {
         this
     }
 
+    /// Get the state at the index in a sequence, or `None` if disabled or out of bounds.
+    pub fn get<'a>(&self, sequence: &'a NextStateSequence<S>) -> Option<&'a S> {
+        self.0.and_then(|index| sequence.get(index))
+    }
+
     /// Set the index and clamp within bounds.
     pub fn seek(&mut self, to: isize, len: usize) {
         self.0 = (len > 0).then(|| to.clamp(0, len as isize - 1) as usize);
@@ -126,6 +290,77 @@ impl<S: State> NextStateIndex<S> {
     pub fn wrapping_prev(&mut self, len: usize) {
         self.wrapping_step(-1, len);
     }
+
+    /// Adjust the index in the current ping-pong direction, bouncing off the ends.
+    ///
+    /// A negative step moves against the current direction.
+    pub fn ping_pong_step(&mut self, by: isize, len: usize) {
+        if len <= 1 {
+            self.0 = (len > 0).then_some(0);
+            self.2 = false;
+            return;
+        }
+
+        // Unfold the bounces into a cycle of period `2 * (len - 1)`.
+        let period = 2 * (len as isize - 1);
+        let index = self.0.unwrap_or_default().min(len - 1) as isize;
+        let pos = if self.2 { period - index } else { index };
+        let pos = (pos + by).rem_euclid(period);
+        self.2 = pos >= len as isize;
+        self.0 = Some(if self.2 { period - pos } else { pos } as usize);
+    }
+
+    /// Step the index by 1 in the current ping-pong direction, bouncing off the ends.
+    pub fn ping_pong_next(&mut self, len: usize) {
+        self.ping_pong_step(1, len);
+    }
+
+    /// Step the index by 1 against the current ping-pong direction, bouncing off the ends.
+    pub fn ping_pong_prev(&mut self, len: usize) {
+        self.ping_pong_step(-1, len);
+    }
+
+    /// Set the index to the first entry with a label.
+    ///
+    /// Returns false and leaves the index unchanged if there is no such entry.
+    pub fn seek_label(&mut self, label: &str, sequence: &NextStateSequence<S>) -> bool {
+        let index = sequence.find_label(label);
+        if index.is_some() {
+            self.0 = index;
+        }
+        index.is_some()
+    }
+
+    /// Set the index to the next entry with a state that matches a pattern.
+    ///
+    /// Returns false and leaves the index unchanged if there is no such entry.
+    pub fn next_matching(
+        &mut self,
+        pattern: &impl StatePattern<S>,
+        sequence: &NextStateSequence<S>,
+    ) -> bool {
+        let index = sequence.find_matching(self.0, pattern, false);
+        if index.is_some() {
+            self.0 = index;
+        }
+        index.is_some()
+    }
+
+    /// Set the index to the next entry with a state that matches a pattern, wrapping around to
+    /// the start of the sequence.
+    ///
+    /// Returns false and leaves the index unchanged if there is no such entry.
+    pub fn wrapping_next_matching(
+        &mut self,
+        pattern: &impl StatePattern<S>,
+        sequence: &NextStateSequence<S>,
+    ) -> bool {
+        let index = sequence.find_matching(self.0, pattern, true);
+        if index.is_some() {
+            self.0 = index;
+        }
+        index.is_some()
+    }
 }
 
 /// An extension trait for [`State`] types with [`NextStateIndex`] as their [`NextState`] type.
@@ -135,7 +370,7 @@ pub trait NextStateIndexMut: State {
         to: isize,
     ) -> impl 'static + Send + Sync + Fn(ResMut<NextStateIndex<Self>>, Res<NextStateSequence<Self>>)
     {
-        move |mut index, sequence| index.seek(to, sequence.0.len())
+        move |mut index, sequence| index.seek(to, sequence.len())
     }
 
     /// A system that adjusts the index and clamps within bounds.
@@ -143,17 +378,17 @@ pub trait NextStateIndexMut: State {
         by: isize,
     ) -> impl 'static + Send + Sync + Fn(ResMut<NextStateIndex<Self>>, Res<NextStateSequence<Self>>)
     {
-        move |mut index, sequence| index.step(by, sequence.0.len())
+        move |mut index, sequence| index.step(by, sequence.len())
     }
 
     /// A system that steps the index forwards by 1 and clamps within bounds.
     fn next(mut index: ResMut<NextStateIndex<Self>>, sequence: Res<NextStateSequence<Self>>) {
-        index.step(1, sequence.0.len());
+        index.step(1, sequence.len());
     }
 
     /// A system that steps the index backwards by 1 and clamps within bounds.
     fn prev(mut index: ResMut<NextStateIndex<Self>>, sequence: Res<NextStateSequence<Self>>) {
-        index.step(-1, sequence.0.len());
+        index.step(-1, sequence.len());
     }
 
     /// A system that sets the index and wraps within bounds.
@@ -161,7 +396,7 @@ pub trait NextStateIndexMut: State {
         to: isize,
     ) -> impl 'static + Send + Sync + Fn(ResMut<NextStateIndex<Self>>, Res<NextStateSequence<Self>>)
     {
-        move |mut index, sequence| index.wrapping_seek(to, sequence.0.len())
+        move |mut index, sequence| index.wrapping_seek(to, sequence.len())
     }
 
     /// A system that adjusts the index and wraps within bounds.
@@ -169,7 +404,7 @@ pub trait NextStateIndexMut: State {
         by: isize,
     ) -> impl 'static + Send + Sync + Fn(ResMut<NextStateIndex<Self>>, Res<NextStateSequence<Self>>)
     {
-        move |mut index, sequence| index.wrapping_step(by, sequence.0.len())
+        move |mut index, sequence| index.wrapping_step(by, sequence.len())
     }
 
     /// A system that steps the index forwards by 1 and wraps within bounds.
@@ -177,7 +412,7 @@ pub trait NextStateIndexMut: State {
         mut index: ResMut<NextStateIndex<Self>>,
         sequence: Res<NextStateSequence<Self>>,
     ) {
-        index.wrapping_step(1, sequence.0.len());
+        index.wrapping_step(1, sequence.len());
     }
 
     /// A system that steps the index backwards by 1 and wraps within bounds.
@@ -185,8 +420,576 @@ pub trait NextStateIndexMut: State {
         mut index: ResMut<NextStateIndex<Self>>,
         sequence: Res<NextStateSequence<Self>>,
     ) {
-        index.wrapping_step(-1, sequence.0.len());
+        index.wrapping_step(-1, sequence.len());
+    }
+
+    /// A system that steps the index by 1 in the current ping-pong direction.
+    fn ping_pong_next(
+        mut index: ResMut<NextStateIndex<Self>>,
+        sequence: Res<NextStateSequence<Self>>,
+    ) {
+        index.ping_pong_step(1, sequence.len());
+    }
+
+    /// A system that steps the index by 1 against the current ping-pong direction.
+    fn ping_pong_prev(
+        mut index: ResMut<NextStateIndex<Self>>,
+        sequence: Res<NextStateSequence<Self>>,
+    ) {
+        index.ping_pong_step(-1, sequence.len());
+    }
+
+    /// A system that adjusts the index in the current ping-pong direction.
+    fn ping_pong_step(
+        by: isize,
+    ) -> impl 'static + Send + Sync + Fn(ResMut<NextStateIndex<Self>>, Res<NextStateSequence<Self>>)
+    {
+        move |mut index, sequence| index.ping_pong_step(by, sequence.len())
+    }
+
+    /// A system that sets the index to the first entry with a label.
+    fn seek_label(
+        label: impl Into<String>,
+    ) -> impl 'static + Send + Sync + Fn(ResMut<NextStateIndex<Self>>, Res<NextStateSequence<Self>>)
+    {
+        let label = label.into();
+        move |mut index, sequence| {
+            index.seek_label(&label, &sequence);
+        }
+    }
+
+    /// A system that sets the index to the next entry with a state that matches a pattern.
+    fn next_matching(
+        pattern: impl StatePattern<Self>,
+    ) -> impl 'static + Send + Sync + Fn(ResMut<NextStateIndex<Self>>, Res<NextStateSequence<Self>>)
+    {
+        move |mut index, sequence| {
+            index.next_matching(&pattern, &sequence);
+        }
+    }
+
+    /// A system that sets the index to the next entry with a state that matches a pattern,
+    /// wrapping around to the start of the sequence.
+    fn wrapping_next_matching(
+        pattern: impl StatePattern<Self>,
+    ) -> impl 'static + Send + Sync + Fn(ResMut<NextStateIndex<Self>>, Res<NextStateSequence<Self>>)
+    {
+        move |mut index, sequence| {
+            index.wrapping_next_matching(&pattern, &sequence);
+        }
     }
 }
 
 impl<S: State<Next = NextStateIndex<S>>> NextStateIndexMut for S {}
+
+/// An extension trait for [`Commands`] that provides methods for operating on states with
+/// [`NextStateIndex`] as their `Next` type.
+pub trait NextStateIndexCommandsExt {
+    /// Queue a command to set the index and clamp within bounds.
+    fn state_seek<S: State<Next = NextStateIndex<S>>>(&mut self, to: isize) -> &mut Self;
+
+    /// Queue a command to adjust the index and clamp within bounds.
+    fn state_step<S: State<Next = NextStateIndex<S>>>(&mut self, by: isize) -> &mut Self;
+
+    /// Queue a command to step the index forwards by 1 and clamp within bounds.
+    fn state_next<S: State<Next = NextStateIndex<S>>>(&mut self) -> &mut Self;
+
+    /// Queue a command to step the index backwards by 1 and clamp within bounds.
+    fn state_prev<S: State<Next = NextStateIndex<S>>>(&mut self) -> &mut Self;
+
+    /// Queue a command to set the index and wrap within bounds.
+    fn state_wrapping_seek<S: State<Next = NextStateIndex<S>>>(&mut self, to: isize) -> &mut Self;
+
+    /// Queue a command to adjust the index and wrap within bounds.
+    fn state_wrapping_step<S: State<Next = NextStateIndex<S>>>(&mut self, by: isize) -> &mut Self;
+
+    /// Queue a command to step the index forwards by 1 and wrap within bounds.
+    fn state_wrapping_next<S: State<Next = NextStateIndex<S>>>(&mut self) -> &mut Self;
+
+    /// Queue a command to step the index backwards by 1 and wrap within bounds.
+    fn state_wrapping_prev<S: State<Next = NextStateIndex<S>>>(&mut self) -> &mut Self;
+
+    /// Queue a command to adjust the index in the current ping-pong direction.
+    fn state_ping_pong_step<S: State<Next = NextStateIndex<S>>>(&mut self, by: isize) -> &mut Self;
+
+    /// Queue a command to step the index by 1 in the current ping-pong direction.
+    fn state_ping_pong_next<S: State<Next = NextStateIndex<S>>>(&mut self) -> &mut Self;
+
+    /// Queue a command to step the index by 1 against the current ping-pong direction.
+    fn state_ping_pong_prev<S: State<Next = NextStateIndex<S>>>(&mut self) -> &mut Self;
+
+    /// Queue a command to set the index to the first entry with a label.
+    fn state_seek_label<S: State<Next = NextStateIndex<S>>>(
+        &mut self,
+        label: impl Into<String>,
+    ) -> &mut Self;
+
+    /// Queue a command to set the index to the next entry with a state that matches a pattern.
+    fn state_next_matching<S: State<Next = NextStateIndex<S>>>(
+        &mut self,
+        pattern: impl StatePattern<S>,
+    ) -> &mut Self;
+
+    /// Queue a command to set the index to the next entry with a state that matches a pattern,
+    /// wrapping around to the start of the sequence.
+    fn state_wrapping_next_matching<S: State<Next = NextStateIndex<S>>>(
+        &mut self,
+        pattern: impl StatePattern<S>,
+    ) -> &mut Self;
+}
+
+/// Queue a command to modify the [`NextStateIndex<S>`] resource, if present.
+fn modify_index<S: State<Next = NextStateIndex<S>>>(
+    commands: &mut Commands,
+    f: impl 'static + Send + FnOnce(&mut NextStateIndex<S>, &NextStateSequence<S>),
+) {
+    commands.queue(move |world: &mut World| {
+        if !world.contains_resource::<NextStateSequence<S>>() {
+            warn_missing::<NextStateSequence<S>>(None);
+            return;
+        }
+        let result = world.try_resource_scope(|world, mut index: Mut<NextStateIndex<S>>| {
+            f(&mut index, world.resource::<NextStateSequence<S>>());
+        });
+        if result.is_none() {
+            warn_missing::<NextStateIndex<S>>(None);
+        }
+    });
+}
+
+impl NextStateIndexCommandsExt for Commands<'_, '_> {
+    fn state_seek<S: State<Next = NextStateIndex<S>>>(&mut self, to: isize) -> &mut Self {
+        modify_index::<S>(self, move |index, sequence| index.seek(to, sequence.len()));
+        self
+    }
+
+    fn state_step<S: State<Next = NextStateIndex<S>>>(&mut self, by: isize) -> &mut Self {
+        modify_index::<S>(self, move |index, sequence| index.step(by, sequence.len()));
+        self
+    }
+
+    fn state_next<S: State<Next = NextStateIndex<S>>>(&mut self) -> &mut Self {
+        modify_index::<S>(self, |index, sequence| index.step(1, sequence.len()));
+        self
+    }
+
+    fn state_prev<S: State<Next = NextStateIndex<S>>>(&mut self) -> &mut Self {
+        modify_index::<S>(self, |index, sequence| index.step(-1, sequence.len()));
+        self
+    }
+
+    fn state_wrapping_seek<S: State<Next = NextStateIndex<S>>>(&mut self, to: isize) -> &mut Self {
+        modify_index::<S>(self, move |index, sequence| {
+            index.wrapping_seek(to, sequence.len())
+        });
+        self
+    }
+
+    fn state_wrapping_step<S: State<Next = NextStateIndex<S>>>(&mut self, by: isize) -> &mut Self {
+        modify_index::<S>(self, move |index, sequence| {
+            index.wrapping_step(by, sequence.len())
+        });
+        self
+    }
+
+    fn state_wrapping_next<S: State<Next = NextStateIndex<S>>>(&mut self) -> &mut Self {
+        modify_index::<S>(self, |index, sequence| {
+            index.wrapping_step(1, sequence.len())
+        });
+        self
+    }
+
+    fn state_wrapping_prev<S: State<Next = NextStateIndex<S>>>(&mut self) -> &mut Self {
+        modify_index::<S>(self, |index, sequence| {
+            index.wrapping_step(-1, sequence.len())
+        });
+        self
+    }
+
+    fn state_ping_pong_step<S: State<Next = NextStateIndex<S>>>(&mut self, by: isize) -> &mut Self {
+        modify_index::<S>(self, move |index, sequence| {
+            index.ping_pong_step(by, sequence.len())
+        });
+        self
+    }
+
+    fn state_ping_pong_next<S: State<Next = NextStateIndex<S>>>(&mut self) -> &mut Self {
+        modify_index::<S>(self, |index, sequence| {
+            index.ping_pong_step(1, sequence.len())
+        });
+        self
+    }
+
+    fn state_ping_pong_prev<S: State<Next = NextStateIndex<S>>>(&mut self) -> &mut Self {
+        modify_index::<S>(self, |index, sequence| {
+            index.ping_pong_step(-1, sequence.len())
+        });
+        self
+    }
+
+    fn state_seek_label<S: State<Next = NextStateIndex<S>>>(
+        &mut self,
+        label: impl Into<String>,
+    ) -> &mut Self {
+        let label = label.into();
+        modify_index::<S>(self, move |index, sequence| {
+            index.seek_label(&label, sequence);
+        });
+        self
+    }
+
+    fn state_next_matching<S: State<Next = NextStateIndex<S>>>(
+        &mut self,
+        pattern: impl StatePattern<S>,
+    ) -> &mut Self {
+        modify_index::<S>(self, move |index, sequence| {
+            index.next_matching(&pattern, sequence);
+        });
+        self
+    }
+
+    fn state_wrapping_next_matching<S: State<Next = NextStateIndex<S>>>(
+        &mut self,
+        pattern: impl StatePattern<S>,
+    ) -> &mut Self {
+        modify_index::<S>(self, move |index, sequence| {
+            index.wrapping_next_matching(&pattern, sequence);
+        });
+        self
+    }
+}
+
+/// A [`NextState`] type that stores the [`State`] type `S` as an index into its own
+/// [`NextStateSequence<S>`].
+///
+/// Unlike [`NextStateIndex<S>`], the sequence isn't shared, so each entity with a local state
+/// can step through its own sequence. Use [`NextStateOwnedSequenceCommandsExt`] to step the
+/// sequence of an entity through commands.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use pyri_state::{next_state::TriggerStateFlush, prelude::*};
+/// #
+/// #[derive(State, Component, Clone, PartialEq, Eq)]
+/// #[state(local, next(NextStateOwnedSequence<Self>))]
+/// enum Routine {
+///     Sleep,
+///     Work,
+///     Eat,
+/// }
+///
+/// fn spawn_npc(mut commands: Commands) {
+///     commands
+///         .spawn_empty()
+///         .insert_state(NextStateOwnedSequence::new(NextStateSequence::new([
+///             Some(Routine::Sleep),
+///             Some(Routine::Work),
+///             Some(Routine::Eat),
+///         ])));
+/// }
+///
+/// fn advance_routines(
+///     mut commands: Commands,
+///     mut npc_query: Query<(Entity, &mut TriggerStateFlush<Routine>)>,
+/// ) {
+///     for (npc, mut trigger) in &mut npc_query {
+///         commands.entity(npc).state_wrapping_next::<Routine>();
+///         trigger.0 = true;
+///     }
+/// }
+/// ```
+#[derive(Resource, Component, Debug)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Resource)
+)]
+pub struct NextStateOwnedSequence<S: State> {
+    /// The sequence of states.
+    pub sequence: NextStateSequence<S>,
+    /// The index into the sequence.
+    pub index: NextStateIndex<S>,
+}
+
+impl<S: State> NextState for NextStateOwnedSequence<S> {
+    type State = S;
+
+    type Param = ();
+
+    fn empty() -> Self {
+        Self {
+            sequence: NextStateSequence::new(Vec::new()),
+            index: NextStateIndex::empty(),
+        }
+    }
+
+    fn next_state<'s>(
+        &'s self,
+        _param: &'s SystemParamItem<Self::Param>,
+    ) -> Option<&'s Self::State> {
+        self.index.get(&self.sequence)
+    }
+}
+
+impl<S: State> NextStateOwnedSequence<S> {
+    /// Create a new `NextStateOwnedSequence` with the index at the start of a sequence.
+    pub fn new(sequence: NextStateSequence<S>) -> Self {
+        let index = NextStateIndex::new(0, sequence.len());
+        Self { sequence, index }
+    }
+
+    /// Set the index and clamp within bounds.
+    pub fn seek(&mut self, to: isize) {
+        self.index.seek(to, self.sequence.len());
+    }
+
+    /// Adjust the index and clamp within bounds.
+    pub fn step(&mut self, by: isize) {
+        self.index.step(by, self.sequence.len());
+    }
+
+    /// Step the index forwards by 1 and clamp within bounds.
+    pub fn next(&mut self) {
+        self.step(1);
+    }
+
+    /// Step the index backwards by 1 and clamp within bounds.
+    pub fn prev(&mut self) {
+        self.step(-1);
+    }
+
+    /// Set the index and wrap within bounds.
+    pub fn wrapping_seek(&mut self, to: isize) {
+        self.index.wrapping_seek(to, self.sequence.len());
+    }
+
+    /// Adjust the index and wrap within bounds.
+    pub fn wrapping_step(&mut self, by: isize) {
+        self.index.wrapping_step(by, self.sequence.len());
+    }
+
+    /// Step the index forwards by 1 and wrap within bounds.
+    pub fn wrapping_next(&mut self) {
+        self.wrapping_step(1);
+    }
+
+    /// Step the index backwards by 1 and wrap within bounds.
+    pub fn wrapping_prev(&mut self) {
+        self.wrapping_step(-1);
+    }
+
+    /// Adjust the index in the current ping-pong direction, bouncing off the ends.
+    pub fn ping_pong_step(&mut self, by: isize) {
+        self.index.ping_pong_step(by, self.sequence.len());
+    }
+
+    /// Step the index by 1 in the current ping-pong direction, bouncing off the ends.
+    pub fn ping_pong_next(&mut self) {
+        self.ping_pong_step(1);
+    }
+
+    /// Step the index by 1 against the current ping-pong direction, bouncing off the ends.
+    pub fn ping_pong_prev(&mut self) {
+        self.ping_pong_step(-1);
+    }
+
+    /// Set the index to the first entry with a label.
+    ///
+    /// Returns false and leaves the index unchanged if there is no such entry.
+    pub fn seek_label(&mut self, label: &str) -> bool {
+        self.index.seek_label(label, &self.sequence)
+    }
+
+    /// Set the index to the next entry with a state that matches a pattern.
+    ///
+    /// Returns false and leaves the index unchanged if there is no such entry.
+    pub fn next_matching(&mut self, pattern: &impl StatePattern<S>) -> bool {
+        self.index.next_matching(pattern, &self.sequence)
+    }
+
+    /// Set the index to the next entry with a state that matches a pattern, wrapping around to
+    /// the start of the sequence.
+    ///
+    /// Returns false and leaves the index unchanged if there is no such entry.
+    pub fn wrapping_next_matching(&mut self, pattern: &impl StatePattern<S>) -> bool {
+        self.index.wrapping_next_matching(pattern, &self.sequence)
+    }
+}
+
+/// An extension trait for [`EntityCommands`] that provides methods for operating on local
+/// states with [`NextStateOwnedSequence`] as their `Next` type.
+pub trait NextStateOwnedSequenceCommandsExt {
+    /// Queue a command to set the index and clamp within bounds.
+    fn state_seek<S: State<Next = NextStateOwnedSequence<S>>>(&mut self, to: isize) -> &mut Self;
+
+    /// Queue a command to adjust the index and clamp within bounds.
+    fn state_step<S: State<Next = NextStateOwnedSequence<S>>>(&mut self, by: isize) -> &mut Self;
+
+    /// Queue a command to step the index forwards by 1 and clamp within bounds.
+    fn state_next<S: State<Next = NextStateOwnedSequence<S>>>(&mut self) -> &mut Self;
+
+    /// Queue a command to step the index backwards by 1 and clamp within bounds.
+    fn state_prev<S: State<Next = NextStateOwnedSequence<S>>>(&mut self) -> &mut Self;
+
+    /// Queue a command to set the index and wrap within bounds.
+    fn state_wrapping_seek<S: State<Next = NextStateOwnedSequence<S>>>(
+        &mut self,
+        to: isize,
+    ) -> &mut Self;
+
+    /// Queue a command to adjust the index and wrap within bounds.
+    fn state_wrapping_step<S: State<Next = NextStateOwnedSequence<S>>>(
+        &mut self,
+        by: isize,
+    ) -> &mut Self;
+
+    /// Queue a command to step the index forwards by 1 and wrap within bounds.
+    fn state_wrapping_next<S: State<Next = NextStateOwnedSequence<S>>>(&mut self) -> &mut Self;
+
+    /// Queue a command to step the index backwards by 1 and wrap within bounds.
+    fn state_wrapping_prev<S: State<Next = NextStateOwnedSequence<S>>>(&mut self) -> &mut Self;
+
+    /// Queue a command to adjust the index in the current ping-pong direction.
+    fn state_ping_pong_step<S: State<Next = NextStateOwnedSequence<S>>>(
+        &mut self,
+        by: isize,
+    ) -> &mut Self;
+
+    /// Queue a command to step the index by 1 in the current ping-pong direction.
+    fn state_ping_pong_next<S: State<Next = NextStateOwnedSequence<S>>>(&mut self) -> &mut Self;
+
+    /// Queue a command to step the index by 1 against the current ping-pong direction.
+    fn state_ping_pong_prev<S: State<Next = NextStateOwnedSequence<S>>>(&mut self) -> &mut Self;
+
+    /// Queue a command to set the index to the first entry with a label.
+    fn state_seek_label<S: State<Next = NextStateOwnedSequence<S>>>(
+        &mut self,
+        label: impl Into<String>,
+    ) -> &mut Self;
+
+    /// Queue a command to set the index to the next entry with a state that matches a pattern.
+    fn state_next_matching<S: State<Next = NextStateOwnedSequence<S>>>(
+        &mut self,
+        pattern: impl StatePattern<S>,
+    ) -> &mut Self;
+
+    /// Queue a command to set the index to the next entry with a state that matches a pattern,
+    /// wrapping around to the start of the sequence.
+    fn state_wrapping_next_matching<S: State<Next = NextStateOwnedSequence<S>>>(
+        &mut self,
+        pattern: impl StatePattern<S>,
+    ) -> &mut Self;
+}
+
+/// Queue a command to modify the [`NextStateOwnedSequence<S>`] of an entity, if present.
+fn modify_owned_sequence<S: State<Next = NextStateOwnedSequence<S>>>(
+    entity: &mut EntityCommands,
+    f: impl 'static + Send + FnOnce(&mut NextStateOwnedSequence<S>),
+) {
+    entity.queue(move |mut entity: EntityWorldMut| {
+        if let Some(mut next) = entity.get_mut::<NextStateOwnedSequence<S>>() {
+            f(&mut next);
+        } else {
+            warn_missing::<NextStateOwnedSequence<S>>(Some(entity.id()));
+        }
+    });
+}
+
+impl NextStateOwnedSequenceCommandsExt for EntityCommands<'_> {
+    fn state_seek<S: State<Next = NextStateOwnedSequence<S>>>(&mut self, to: isize) -> &mut Self {
+        modify_owned_sequence::<S>(self, move |next| next.seek(to));
+        self
+    }
+
+    fn state_step<S: State<Next = NextStateOwnedSequence<S>>>(&mut self, by: isize) -> &mut Self {
+        modify_owned_sequence::<S>(self, move |next| next.step(by));
+        self
+    }
+
+    fn state_next<S: State<Next = NextStateOwnedSequence<S>>>(&mut self) -> &mut Self {
+        modify_owned_sequence::<S>(self, NextStateOwnedSequence::next);
+        self
+    }
+
+    fn state_prev<S: State<Next = NextStateOwnedSequence<S>>>(&mut self) -> &mut Self {
+        modify_owned_sequence::<S>(self, NextStateOwnedSequence::prev);
+        self
+    }
+
+    fn state_wrapping_seek<S: State<Next = NextStateOwnedSequence<S>>>(
+        &mut self,
+        to: isize,
+    ) -> &mut Self {
+        modify_owned_sequence::<S>(self, move |next| next.wrapping_seek(to));
+        self
+    }
+
+    fn state_wrapping_step<S: State<Next = NextStateOwnedSequence<S>>>(
+        &mut self,
+        by: isize,
+    ) -> &mut Self {
+        modify_owned_sequence::<S>(self, move |next| next.wrapping_step(by));
+        self
+    }
+
+    fn state_wrapping_next<S: State<Next = NextStateOwnedSequence<S>>>(&mut self) -> &mut Self {
+        modify_owned_sequence::<S>(self, NextStateOwnedSequence::wrapping_next);
+        self
+    }
+
+    fn state_wrapping_prev<S: State<Next = NextStateOwnedSequence<S>>>(&mut self) -> &mut Self {
+        modify_owned_sequence::<S>(self, NextStateOwnedSequence::wrapping_prev);
+        self
+    }
+
+    fn state_ping_pong_step<S: State<Next = NextStateOwnedSequence<S>>>(
+        &mut self,
+        by: isize,
+    ) -> &mut Self {
+        modify_owned_sequence::<S>(self, move |next| next.ping_pong_step(by));
+        self
+    }
+
+    fn state_ping_pong_next<S: State<Next = NextStateOwnedSequence<S>>>(&mut self) -> &mut Self {
+        modify_owned_sequence::<S>(self, NextStateOwnedSequence::ping_pong_next);
+        self
+    }
+
+    fn state_ping_pong_prev<S: State<Next = NextStateOwnedSequence<S>>>(&mut self) -> &mut Self {
+        modify_owned_sequence::<S>(self, NextStateOwnedSequence::ping_pong_prev);
+        self
+    }
+
+    fn state_seek_label<S: State<Next = NextStateOwnedSequence<S>>>(
+        &mut self,
+        label: impl Into<String>,
+    ) -> &mut Self {
+        let label = label.into();
+        modify_owned_sequence::<S>(self, move |next| {
+            next.seek_label(&label);
+        });
+        self
+    }
+
+    fn state_next_matching<S: State<Next = NextStateOwnedSequence<S>>>(
+        &mut self,
+        pattern: impl StatePattern<S>,
+    ) -> &mut Self {
+        modify_owned_sequence::<S>(self, move |next| {
+            next.next_matching(&pattern);
+        });
+        self
+    }
+
+    fn state_wrapping_next_matching<S: State<Next = NextStateOwnedSequence<S>>>(
+        &mut self,
+        pattern: impl StatePattern<S>,
+    ) -> &mut Self {
+        modify_owned_sequence::<S>(self, move |next| {
+            next.wrapping_next_matching(&pattern);
+        });
+        self
+    }
+}
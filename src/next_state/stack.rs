@@ -11,12 +11,12 @@ use bevy_ecs::reflect::ReflectResource;
 use bevy_ecs::{
     component::Component,
     resource::Resource,
-    system::{Commands, ResMut, SystemParamItem},
-    world::{FromWorld, World},
+    system::{Commands, EntityCommands, ResMut, SystemParamItem},
+    world::{EntityWorldMut, FromWorld, World},
 };
 
 use crate::{
-    next_state::{NextState, NextStateMut},
+    next_state::{NextState, NextStateMut, warn_missing},
     state::State,
 };
 
@@ -215,8 +215,10 @@ pub trait NextStateStackMutExtClone: NextStateStackMut + Clone {
 
 impl<S: NextStateStackMut + Clone> NextStateStackMutExtClone for S {}
 
-/// An extension trait for [`Commands`] that provides methods for operating on states with
-/// [`NextStateStack`] as their `Next` type.
+/// An extension trait for [`Commands`] and [`EntityCommands`] that provides methods for
+/// operating on states with [`NextStateStack`] as their `Next` type.
+///
+/// With `EntityCommands`, the methods operate on the local state of the entity instead.
 pub trait NextStateStackCommandsExt {
     /// Queues a [`Command`](bevy_ecs::system::Command) to push a new base state index to the stack.
     fn state_stack_acquire<S: State<Next = NextStateStack<S>>>(&mut self) -> &mut Self;
@@ -244,38 +246,120 @@ pub trait NextStateStackCommandsExt {
     fn state_stack_pop_push<S: State<Next = NextStateStack<S>>>(&mut self, state: S) -> &mut Self;
 }
 
+/// Queue a command to modify the [`NextStateStack<S>`] resource, if present.
+fn modify_stack<S: State<Next = NextStateStack<S>>>(
+    commands: &mut Commands,
+    f: impl 'static + Send + FnOnce(&mut NextStateStack<S>),
+) {
+    commands.queue(move |world: &mut World| {
+        if let Some(mut stack) = world.get_resource_mut::<NextStateStack<S>>() {
+            f(&mut stack);
+        } else {
+            warn_missing::<NextStateStack<S>>(None);
+        }
+    });
+}
+
 impl NextStateStackCommandsExt for Commands<'_, '_> {
     fn state_stack_acquire<S: State<Next = NextStateStack<S>>>(&mut self) -> &mut Self {
-        self.queue(move |world: &mut World| {
-            world.resource_mut::<NextStateStack<S>>().acquire();
+        modify_stack::<S>(self, |stack| {
+            stack.acquire();
+        });
+        self
+    }
+
+    fn state_stack_release<S: State<Next = NextStateStack<S>>>(&mut self) -> &mut Self {
+        modify_stack::<S>(self, |stack| {
+            stack.release();
+        });
+        self
+    }
+
+    fn state_stack_clear<S: State<Next = NextStateStack<S>>>(&mut self) -> &mut Self {
+        modify_stack::<S>(self, |stack| {
+            stack.clear();
+        });
+        self
+    }
+
+    fn state_stack_pop<S: State<Next = NextStateStack<S>>>(&mut self) -> &mut Self {
+        modify_stack::<S>(self, |stack| {
+            stack.pop();
+        });
+        self
+    }
+
+    fn state_stack_push<S: State<Next = NextStateStack<S>>>(&mut self, state: S) -> &mut Self {
+        modify_stack::<S>(self, move |stack| {
+            stack.push(state);
+        });
+        self
+    }
+
+    fn state_stack_clear_push<S: State<Next = NextStateStack<S>>>(
+        &mut self,
+        state: S,
+    ) -> &mut Self {
+        modify_stack::<S>(self, move |stack| {
+            stack.clear().push(state);
+        });
+        self
+    }
+
+    fn state_stack_pop_push<S: State<Next = NextStateStack<S>>>(&mut self, state: S) -> &mut Self {
+        modify_stack::<S>(self, move |stack| {
+            stack.pop().push(state);
+        });
+        self
+    }
+}
+
+/// Queue a command to modify the local [`NextStateStack<S>`] of an entity, if present.
+fn modify_local_stack<S: State<Next = NextStateStack<S>>>(
+    entity: &mut EntityCommands,
+    f: impl 'static + Send + FnOnce(&mut NextStateStack<S>),
+) {
+    entity.queue(move |mut entity: EntityWorldMut| {
+        if let Some(mut stack) = entity.get_mut::<NextStateStack<S>>() {
+            f(&mut stack);
+        } else {
+            warn_missing::<NextStateStack<S>>(Some(entity.id()));
+        }
+    });
+}
+
+impl NextStateStackCommandsExt for EntityCommands<'_> {
+    fn state_stack_acquire<S: State<Next = NextStateStack<S>>>(&mut self) -> &mut Self {
+        modify_local_stack::<S>(self, |stack| {
+            stack.acquire();
         });
         self
     }
 
     fn state_stack_release<S: State<Next = NextStateStack<S>>>(&mut self) -> &mut Self {
-        self.queue(move |world: &mut World| {
-            world.resource_mut::<NextStateStack<S>>().release();
+        modify_local_stack::<S>(self, |stack| {
+            stack.release();
         });
         self
     }
 
     fn state_stack_clear<S: State<Next = NextStateStack<S>>>(&mut self) -> &mut Self {
-        self.queue(move |world: &mut World| {
-            world.resource_mut::<NextStateStack<S>>().clear();
+        modify_local_stack::<S>(self, |stack| {
+            stack.clear();
         });
         self
     }
 
     fn state_stack_pop<S: State<Next = NextStateStack<S>>>(&mut self) -> &mut Self {
-        self.queue(move |world: &mut World| {
-            world.resource_mut::<NextStateStack<S>>().pop();
+        modify_local_stack::<S>(self, |stack| {
+            stack.pop();
         });
         self
     }
 
     fn state_stack_push<S: State<Next = NextStateStack<S>>>(&mut self, state: S) -> &mut Self {
-        self.queue(move |world: &mut World| {
-            world.resource_mut::<NextStateStack<S>>().push(state);
+        modify_local_stack::<S>(self, move |stack| {
+            stack.push(state);
         });
         self
     }
@@ -284,18 +368,15 @@ impl NextStateStackCommandsExt for Commands<'_, '_> {
         &mut self,
         state: S,
     ) -> &mut Self {
-        self.queue(move |world: &mut World| {
-            world
-                .resource_mut::<NextStateStack<S>>()
-                .clear()
-                .push(state);
+        modify_local_stack::<S>(self, move |stack| {
+            stack.clear().push(state);
         });
         self
     }
 
     fn state_stack_pop_push<S: State<Next = NextStateStack<S>>>(&mut self, state: S) -> &mut Self {
-        self.queue(move |world: &mut World| {
-            world.resource_mut::<NextStateStack<S>>().pop().push(state);
+        modify_local_stack::<S>(self, move |stack| {
+            stack.pop().push(state);
         });
         self
     }
@@ -1,19 +1,31 @@
 //! State pattern-matching tools.
 //!
 //! Use the [`state!`](crate::state!) macro to build [`StatePattern`] and
-//! [`StateTransPattern`] instances.
+//! [`StateTransPattern`] instances, combine patterns with methods such as
+//! [`StatePattern::or`] and [`StatePattern::except`], and use a tuple of `StatePattern` types
+//! for different [`State`] types as a [`StatePatternTuple`].
 
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use core::marker::PhantomData;
 
 use bevy_ecs::{
+    component::{Component, Mutable},
+    entity::Entity,
+    event::EventWriter,
     schedule::{Condition, IntoScheduleConfigs, ScheduleConfigs},
-    system::ScheduleSystem,
+    system::{
+        In, IntoSystem, ReadOnlySystemParam, ScheduleSystem, StaticSystemParam, System as _,
+        SystemParamItem, SystemState,
+    },
+    world::World,
 };
 
 use crate::{
-    access::{CurrentRef, FlushRef, NextRef},
-    schedule::ResolveStateSet,
-    state::State,
+    access::{CurrentRef, FlushMut, FlushRef, LocalFlushRef},
+    next_state::{NextStateMut, TriggerStateFlush},
+    schedule::{ApplyFlushSet, ResolveStateSet, resolve_state::StateFlushRejected},
+    setup::set_local_next_state,
+    state::{LocalState, State, StateMut},
 };
 
 /// A type that can match a subset of values of the [`State`] type `S`.
@@ -24,13 +36,21 @@ use crate::{
 ///
 /// - [`StatePatternExtClone<S>`]
 /// - [`StatePatternExtEq<S>`]
+/// - [`StatePatternExtLocal<S>`]
 pub trait StatePattern<S: State>: 'static + Send + Sync + Sized {
+    /// Whether the exit and enter hooks of this pattern are skipped when it matches both the
+    /// old and new states of a [hierarchical](State::HIERARCHICAL) state type.
+    ///
+    /// This is `false` for [`AnyStatePattern`], so [`State::ANY`] hooks still run on every
+    /// exit and enter.
+    const HIERARCHICAL: bool = true;
+
     /// Check if the pattern matches a particular state.
     fn matches(&self, state: &S) -> bool;
 
     /// Build a run condition that checks if `S` is in a matching state.
     fn will_update(self) -> impl 'static + Send + Sync + Fn(CurrentRef<S>) -> bool {
-        self.will_exit()
+        move |state| state.is_in(&self)
     }
 
     /// Configure systems to run if `S` is in a matching state.
@@ -42,8 +62,8 @@ pub trait StatePattern<S: State>: 'static + Send + Sync + Sized {
     }
 
     /// Build a run condition that checks if `S` will exit a matching state if triggered.
-    fn will_exit(self) -> impl 'static + Send + Sync + Fn(CurrentRef<S>) -> bool {
-        move |state| state.is_in(&self)
+    fn will_exit(self) -> impl 'static + Send + Sync + Fn(FlushRef<S>) -> bool {
+        move |state| state.will_exit(&self)
     }
 
     /// Configure systems to run when `S` exits a matching state.
@@ -74,8 +94,8 @@ pub trait StatePattern<S: State>: 'static + Send + Sync + Sized {
     }
 
     /// Build a run condition that checks if `S` will enter into a matching state if triggered.
-    fn will_enter(self) -> impl 'static + Send + Sync + Fn(NextRef<S>) -> bool {
-        move |state| state.will_be_in(&self)
+    fn will_enter(self) -> impl 'static + Send + Sync + Fn(FlushRef<S>) -> bool {
+        move |state| state.will_enter(&self)
     }
 
     /// Configure systems to run when `S` enters a matching state.
@@ -104,6 +124,51 @@ pub trait StatePattern<S: State>: 'static + Send + Sync + Sized {
             .in_set(ResolveStateSet::<S>::AnyFlush)
             .in_set(ResolveStateSet::<S>::Enter)
     }
+
+    /// Configure a guard system to run when `S` is triggered to become disabled from a matching
+    /// state.
+    ///
+    /// See [`StateTransPattern::guard`] for how guards work.
+    fn guard_disable<M>(
+        self,
+        guard: impl IntoSystem<(), bool, M>,
+    ) -> ScheduleConfigs<ScheduleSystem>
+    where
+        S: StateMut + Clone,
+    {
+        guard_flush::<S, _, _>(guard, self.will_disable())
+    }
+
+    /// Configure a guard system to run when `S` is triggered to become enabled in a matching
+    /// state.
+    ///
+    /// See [`StateTransPattern::guard`] for how guards work.
+    fn guard_enable<M>(self, guard: impl IntoSystem<(), bool, M>) -> ScheduleConfigs<ScheduleSystem>
+    where
+        S: StateMut + Clone,
+    {
+        guard_flush::<S, _, _>(guard, self.will_enable())
+    }
+
+    /// Build a pattern that matches if this pattern or another pattern matches.
+    fn or<P: StatePattern<S>>(self, other: P) -> OrPattern<Self, P> {
+        OrPattern(self, other)
+    }
+
+    /// Build a pattern that matches if this pattern and another pattern both match.
+    fn and<P: StatePattern<S>>(self, other: P) -> AndPattern<Self, P> {
+        AndPattern(self, other)
+    }
+
+    /// Build a pattern that matches if this pattern doesn't match.
+    fn not(self) -> NotPattern<Self> {
+        NotPattern(self)
+    }
+
+    /// Build a pattern that matches if this pattern matches and another pattern doesn't.
+    fn except<P: StatePattern<S>>(self, other: P) -> AndPattern<Self, NotPattern<P>> {
+        AndPattern(self, NotPattern(other))
+    }
 }
 
 /// An extension trait for [`StatePattern`] types that also implement `Clone`.
@@ -146,6 +211,240 @@ pub trait StatePatternExtEq<S: State + Eq>: StatePattern<S> {
 
 impl<S: State + Eq, P: StatePattern<S>> StatePatternExtEq<S> for P {}
 
+/// An extension trait for [`StatePattern<S>`] when `S` is a [`LocalState`].
+///
+/// Systems configured by these methods take an `In<Vec<Entity>>` with the entities whose local
+/// state matched, and only run if there's at least one such entity.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use pyri_state::prelude::*;
+/// #
+/// # #[derive(State, Component, Clone, PartialEq, Eq)]
+/// # #[state(local)]
+/// # enum EnemyAi {
+/// #     Idle,
+/// #     Chase,
+/// # }
+/// #
+/// fn play_alert_sound(In(entities): In<Vec<Entity>>) {
+///     info!("{} enemies started chasing!", entities.len());
+/// }
+///
+/// # fn plugin(app: &mut App) {
+/// app.add_systems(StateFlush, EnemyAi::Chase.on_local_enter(play_alert_sound));
+/// # }
+/// ```
+pub trait StatePatternExtLocal<S: LocalState>: StatePattern<S> {
+    /// Build a run condition that checks if any entity's `S` is in a matching state.
+    fn will_local_update(self) -> impl 'static + Send + Sync + Fn(LocalFlushRef<S>) -> bool {
+        move |state| state.iter_in(&self).next().is_some()
+    }
+
+    /// Configure a system to run with the entities whose `S` is in a matching state.
+    fn on_local_update<M>(
+        self,
+        system: impl IntoSystem<In<Vec<Entity>>, (), M>,
+    ) -> ScheduleConfigs<ScheduleSystem> {
+        let pattern = Arc::new(self);
+        local_hook(
+            pattern.clone(),
+            |state, pattern| state.iter_in(pattern).collect(),
+            system,
+        )
+        .run_if(move |state: LocalFlushRef<S>| state.iter_in(&*pattern).next().is_some())
+    }
+
+    /// Build a run condition that checks if any entity's `S` will exit a matching state if
+    /// triggered.
+    fn will_local_exit(self) -> impl 'static + Send + Sync + Fn(LocalFlushRef<S>) -> bool {
+        move |state| state.iter_exit(&self).next().is_some()
+    }
+
+    /// Configure a system to run with the entities whose `S` exits a matching state.
+    fn on_local_exit<M>(
+        self,
+        system: impl IntoSystem<In<Vec<Entity>>, (), M>,
+    ) -> ScheduleConfigs<ScheduleSystem> {
+        let pattern = Arc::new(self);
+        local_hook(
+            pattern.clone(),
+            |state, pattern| state.iter_exit(pattern).collect(),
+            system,
+        )
+        .run_if(move |state: LocalFlushRef<S>| state.iter_exit(&*pattern).next().is_some())
+        .in_set(ResolveStateSet::<S>::Exit)
+    }
+
+    /// Build a run condition that checks if any entity's `S` will become disabled from a
+    /// matching state if triggered.
+    fn will_local_disable(self) -> impl 'static + Send + Sync + Fn(LocalFlushRef<S>) -> bool {
+        move |state| state.iter_disable(&self).next().is_some()
+    }
+
+    /// Configure a system to run with the entities whose `S` is disabled from a matching state.
+    fn on_local_disable<M>(
+        self,
+        system: impl IntoSystem<In<Vec<Entity>>, (), M>,
+    ) -> ScheduleConfigs<ScheduleSystem> {
+        let pattern = Arc::new(self);
+        local_hook(
+            pattern.clone(),
+            |state, pattern| state.iter_disable(pattern).collect(),
+            system,
+        )
+        .run_if(move |state: LocalFlushRef<S>| state.iter_disable(&*pattern).next().is_some())
+        .in_set(ResolveStateSet::<S>::Exit)
+    }
+
+    /// Build a run condition that checks if any entity's `S` will enter a matching state if
+    /// triggered.
+    fn will_local_enter(self) -> impl 'static + Send + Sync + Fn(LocalFlushRef<S>) -> bool {
+        move |state| state.iter_enter(&self).next().is_some()
+    }
+
+    /// Configure a system to run with the entities whose `S` enters a matching state.
+    fn on_local_enter<M>(
+        self,
+        system: impl IntoSystem<In<Vec<Entity>>, (), M>,
+    ) -> ScheduleConfigs<ScheduleSystem> {
+        let pattern = Arc::new(self);
+        local_hook(
+            pattern.clone(),
+            |state, pattern| state.iter_enter(pattern).collect(),
+            system,
+        )
+        .run_if(move |state: LocalFlushRef<S>| state.iter_enter(&*pattern).next().is_some())
+        .in_set(ResolveStateSet::<S>::Enter)
+    }
+
+    /// Build a run condition that checks if any entity's `S` will become enabled in a matching
+    /// state if triggered.
+    fn will_local_enable(self) -> impl 'static + Send + Sync + Fn(LocalFlushRef<S>) -> bool {
+        move |state| state.iter_enable(&self).next().is_some()
+    }
+
+    /// Configure a system to run with the entities whose `S` becomes enabled in a matching
+    /// state.
+    fn on_local_enable<M>(
+        self,
+        system: impl IntoSystem<In<Vec<Entity>>, (), M>,
+    ) -> ScheduleConfigs<ScheduleSystem> {
+        let pattern = Arc::new(self);
+        local_hook(
+            pattern.clone(),
+            |state, pattern| state.iter_enable(pattern).collect(),
+            system,
+        )
+        .run_if(move |state: LocalFlushRef<S>| state.iter_enable(&*pattern).next().is_some())
+        .in_set(ResolveStateSet::<S>::Enter)
+    }
+
+    /// Configure a guard system to run for each entity whose `S` is triggered to become
+    /// disabled from a matching state.
+    ///
+    /// See [`StateTransPatternExtLocal::local_guard`] for how local guards work.
+    fn local_guard_disable<M>(
+        self,
+        guard: impl IntoSystem<In<Entity>, bool, M>,
+    ) -> ScheduleConfigs<ScheduleSystem>
+    where
+        S: Clone + LocalState<Next: NextStateMut + Component<Mutability = Mutable>>,
+    {
+        local_guard(
+            self,
+            |state, pattern| state.iter_disable(pattern).collect(),
+            guard,
+        )
+    }
+
+    /// Configure a guard system to run for each entity whose `S` is triggered to become
+    /// enabled in a matching state.
+    ///
+    /// See [`StateTransPatternExtLocal::local_guard`] for how local guards work.
+    fn local_guard_enable<M>(
+        self,
+        guard: impl IntoSystem<In<Entity>, bool, M>,
+    ) -> ScheduleConfigs<ScheduleSystem>
+    where
+        S: Clone + LocalState<Next: NextStateMut + Component<Mutability = Mutable>>,
+    {
+        local_guard(
+            self,
+            |state, pattern| state.iter_enable(pattern).collect(),
+            guard,
+        )
+    }
+}
+
+impl<S: LocalState, P: StatePattern<S>> StatePatternExtLocal<S> for P {}
+
+// Pipe the entities selected by `select` into a local hook system.
+fn local_hook<S: LocalState, P: 'static + Send + Sync, M>(
+    pattern: Arc<P>,
+    select: fn(&LocalFlushRef<S>, &P) -> Vec<Entity>,
+    system: impl IntoSystem<In<Vec<Entity>>, (), M>,
+) -> ScheduleConfigs<ScheduleSystem> {
+    IntoSystem::into_system(move |state: LocalFlushRef<S>| select(&state, &pattern))
+        .pipe(system)
+        .into_configs()
+}
+
+// Run a guard system for each entity selected by `select`, and reject the flush for each
+// entity where the guard returns `false`.
+fn local_guard<S, P, M>(
+    pattern: P,
+    select: fn(&LocalFlushRef<S>, &P) -> Vec<Entity>,
+    guard: impl IntoSystem<In<Entity>, bool, M>,
+) -> ScheduleConfigs<ScheduleSystem>
+where
+    S: Clone + LocalState<Next: NextStateMut + Component<Mutability = Mutable>>,
+    P: 'static + Send + Sync,
+{
+    let mut guard = IntoSystem::into_system(guard);
+    let name = guard.name();
+    let mut initialized = false;
+    let run_guard = move |world: &mut World, state: &mut SystemState<LocalFlushRef<S>>| {
+        let entities = select(&state.get(world), &pattern);
+        if entities.is_empty() {
+            return;
+        }
+        if !initialized {
+            guard.initialize(world);
+            initialized = true;
+        }
+
+        for entity in entities {
+            if guard.run(entity, world) {
+                continue;
+            }
+
+            let Some((old, new)) = state
+                .get(world)
+                .get(entity)
+                .map(|(old, new)| (old.cloned(), new.cloned()))
+            else {
+                continue;
+            };
+            let mut entity_mut = world.entity_mut(entity);
+            set_local_next_state(&mut entity_mut, old.clone());
+            if let Some(mut trigger) = entity_mut.get_mut::<TriggerStateFlush<S>>() {
+                trigger.0 = false;
+            }
+            world.send_event(StateFlushRejected {
+                guard: name.clone(),
+                entity: Some(entity),
+                old,
+                new,
+            });
+        }
+    };
+
+    run_guard.in_set(ResolveStateSet::<S>::Guard)
+}
+
 impl<S: State + Eq> StatePattern<S> for S {
     fn matches(&self, state: &S) -> bool {
         self == state
@@ -179,6 +478,8 @@ impl<S: State> Clone for AnyStatePattern<S> {
 
 // TODO: Optimization: Instead of impling the trait, raw impl the methods and use `AnyExit` etc. system sets.
 impl<S: State> StatePattern<S> for AnyStatePattern<S> {
+    const HIERARCHICAL: bool = false;
+
     fn matches(&self, _state: &S) -> bool {
         true
     }
@@ -229,6 +530,170 @@ where
     }
 }
 
+/// A type-erased [`StatePattern`] for the [`State`] type `S`.
+///
+/// This can be used to store patterns of different types in the same place, such as in a
+/// component.
+pub struct BoxedStatePattern<S: State>(Box<dyn 'static + Send + Sync + Fn(&S) -> bool>);
+
+impl<S: State> StatePattern<S> for BoxedStatePattern<S> {
+    fn matches(&self, state: &S) -> bool {
+        self.0(state)
+    }
+}
+
+impl<S: State> BoxedStatePattern<S> {
+    /// Create a new `BoxedStatePattern` from a pattern.
+    pub fn new(pattern: impl StatePattern<S>) -> Self {
+        Self(Box::new(move |state| pattern.matches(state)))
+    }
+}
+
+/// A tuple of [`StatePattern`] types for different [`State`] types that matches when every
+/// pattern matches its state.
+///
+/// The type parameter `S` is the corresponding tuple of `State` types. Tuples of up to 4
+/// patterns are supported.
+///
+/// A tuple of two `StatePattern` types for the same `State` type is also a
+/// [`StateTransPattern`], so the hooks that run when the combined pattern starts or stops
+/// matching are named [`on_enter_all`](Self::on_enter_all) and
+/// [`on_exit_all`](Self::on_exit_all) instead.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use pyri_state::prelude::*;
+/// #
+/// # #[derive(State, Clone, PartialEq, Eq)]
+/// # enum Screen {
+/// #     Title,
+/// #     Gameplay,
+/// # }
+/// #
+/// # #[derive(State, Clone, PartialEq, Eq)]
+/// # enum Paused {
+/// #     On,
+/// #     Off,
+/// # }
+/// #
+/// # fn tick_physics() {}
+/// # fn resume_music() {}
+/// #
+/// # fn plugin(app: &mut App) {
+/// app.add_systems(Update, tick_physics.run_if((state!(Screen::Gameplay), Paused::Off).will_update()));
+/// app.add_systems(StateFlush, (Screen::Gameplay, Paused::Off).on_enter_all(resume_music));
+/// # }
+/// ```
+pub trait StatePatternTuple<S>: 'static + Send + Sync + Sized {
+    /// A [`SystemParam`] with read-only access to the current and next values of every state.
+    type Param: ReadOnlySystemParam + 'static;
+
+    /// Check if every pattern matches the current value of its state.
+    fn matches_current(&self, param: &SystemParamItem<Self::Param>) -> bool;
+
+    /// Check if every pattern matches the value of its state after the flush, which is the
+    /// next value if triggered, or the current value otherwise.
+    fn matches_flushed(&self, param: &SystemParamItem<Self::Param>) -> bool;
+
+    /// Configure systems to run after the [`ResolveStateSet::Flush`] system set of every state.
+    fn after_flush<M>(
+        systems: impl IntoScheduleConfigs<ScheduleSystem, M>,
+    ) -> ScheduleConfigs<ScheduleSystem>;
+
+    /// Build a run condition that checks if every state is in a matching state.
+    fn will_update(
+        self,
+    ) -> impl 'static + Send + Sync + Fn(StaticSystemParam<Self::Param>) -> bool {
+        move |param| self.matches_current(&param)
+    }
+
+    /// Configure systems to run if every state is in a matching state.
+    fn on_update<M>(
+        self,
+        systems: impl IntoScheduleConfigs<ScheduleSystem, M>,
+    ) -> ScheduleConfigs<ScheduleSystem> {
+        systems.run_if(self.will_update())
+    }
+
+    /// Build a run condition that checks if the combined pattern will stop matching when the
+    /// triggered states flush.
+    fn will_exit_all(
+        self,
+    ) -> impl 'static + Send + Sync + Fn(StaticSystemParam<Self::Param>) -> bool {
+        move |param| self.matches_current(&param) && !self.matches_flushed(&param)
+    }
+
+    /// Configure systems to run when the combined pattern stops matching during a flush of any
+    /// of the states.
+    ///
+    /// The systems run after the on-flush hooks of every state.
+    fn on_exit_all<M>(
+        self,
+        systems: impl IntoScheduleConfigs<ScheduleSystem, M>,
+    ) -> ScheduleConfigs<ScheduleSystem> {
+        Self::after_flush(systems.run_if(self.will_exit_all()))
+    }
+
+    /// Build a run condition that checks if the combined pattern will start matching when the
+    /// triggered states flush.
+    fn will_enter_all(
+        self,
+    ) -> impl 'static + Send + Sync + Fn(StaticSystemParam<Self::Param>) -> bool {
+        move |param| !self.matches_current(&param) && self.matches_flushed(&param)
+    }
+
+    /// Configure systems to run when the combined pattern starts matching during a flush of any
+    /// of the states.
+    ///
+    /// The systems run after the on-flush hooks of every state.
+    fn on_enter_all<M>(
+        self,
+        systems: impl IntoScheduleConfigs<ScheduleSystem, M>,
+    ) -> ScheduleConfigs<ScheduleSystem> {
+        Self::after_flush(systems.run_if(self.will_enter_all()))
+    }
+}
+
+/// Get the value of a state after the flush, which is the next value if triggered, or the
+/// current value otherwise.
+fn flushed<'a, S: State>(state: &'a FlushRef<S>) -> Option<&'a S> {
+    if state.next.is_triggered() {
+        state.next.get()
+    } else {
+        state.current.get()
+    }
+}
+
+macro_rules! impl_state_pattern_tuple {
+    ($(($S:ident, $P:ident, $i:tt)),*) => {
+        impl<$($S: State, $P: StatePattern<$S>),*> StatePatternTuple<($($S,)*)> for ($($P,)*) {
+            type Param = ($(FlushRef<'static, 'static, $S>,)*);
+
+            fn matches_current(&self, param: &SystemParamItem<Self::Param>) -> bool {
+                $(param.$i.current.is_in(&self.$i))&&*
+            }
+
+            fn matches_flushed(&self, param: &SystemParamItem<Self::Param>) -> bool {
+                $(flushed(&param.$i).is_some_and(|x| self.$i.matches(x)))&&*
+            }
+
+            fn after_flush<M>(
+                systems: impl IntoScheduleConfigs<ScheduleSystem, M>,
+            ) -> ScheduleConfigs<ScheduleSystem> {
+                systems
+                    $(.after(ResolveStateSet::<$S>::Flush))*
+                    .before(ApplyFlushSet)
+            }
+        }
+    };
+}
+
+impl_state_pattern_tuple!((S0, P0, 0), (S1, P1, 1));
+impl_state_pattern_tuple!((S0, P0, 0), (S1, P1, 1), (S2, P2, 2));
+impl_state_pattern_tuple!((S0, P0, 0), (S1, P1, 1), (S2, P2, 2), (S3, P3, 3));
+
 /// A type that can match a subset of transitions in the [`State`] type `S`.
 ///
 /// A tuple of two [`StatePattern`] types can be used as a transition pattern.
@@ -236,6 +701,7 @@ where
 /// See the following extension traits with additional bounds on `Self`:
 ///
 /// - [`StateTransPatternExtClone`]
+/// - [`StateTransPatternExtLocal`]
 pub trait StateTransPattern<S: State>: 'static + Send + Sync + Sized {
     /// Check if the pattern matches a particular pair of states.
     fn matches(&self, old: &S, new: &S) -> bool;
@@ -277,6 +743,66 @@ pub trait StateTransPattern<S: State>: 'static + Send + Sync + Sized {
             .in_set(ResolveStateSet::<S>::AnyFlush)
             .in_set(ResolveStateSet::<S>::Enter)
     }
+
+    /// Configure a guard system to run when `S` is triggered to undergo a matching transition.
+    ///
+    /// The guard system returns `true` to allow the flush or `false` to reject it, and can
+    /// rewrite the next state before allowing the flush. On rejection, the next state will be
+    /// reset to the current state and a [`StateFlushRejected<S>`] event will be sent.
+    ///
+    /// To guard enabling or disabling `S`, use [`StatePattern::guard_enable`] or
+    /// [`StatePattern::guard_disable`]. To guard a local state, use
+    /// [`StateTransPatternExtLocal::local_guard`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use pyri_state::prelude::*;
+    /// #
+    /// # #[derive(State, Clone, PartialEq, Eq)]
+    /// # enum Screen {
+    /// #     Title,
+    /// #     Gameplay,
+    /// # }
+    /// #
+    /// # #[derive(Resource)]
+    /// # struct SaveInProgress(bool);
+    /// #
+    /// fn save_finished(save: Res<SaveInProgress>) -> bool {
+    ///     !save.0
+    /// }
+    ///
+    /// # fn plugin(app: &mut App) {
+    /// app.add_systems(StateFlush, (Screen::Gameplay, Screen::Title).guard(save_finished));
+    /// # }
+    /// ```
+    fn guard<M>(self, guard: impl IntoSystem<(), bool, M>) -> ScheduleConfigs<ScheduleSystem>
+    where
+        S: StateMut + Clone,
+    {
+        guard_flush::<S, _, _>(guard, self.will_trans())
+    }
+
+    /// Build a pattern that matches if this pattern or another pattern matches.
+    fn or<P: StateTransPattern<S>>(self, other: P) -> OrPattern<Self, P> {
+        OrPattern(self, other)
+    }
+
+    /// Build a pattern that matches if this pattern and another pattern both match.
+    fn and<P: StateTransPattern<S>>(self, other: P) -> AndPattern<Self, P> {
+        AndPattern(self, other)
+    }
+
+    /// Build a pattern that matches if this pattern doesn't match.
+    fn not(self) -> NotPattern<Self> {
+        NotPattern(self)
+    }
+
+    /// Build a pattern that matches if this pattern matches and another pattern doesn't.
+    fn except<P: StateTransPattern<S>>(self, other: P) -> AndPattern<Self, NotPattern<P>> {
+        AndPattern(self, NotPattern(other))
+    }
 }
 
 /// An extension trait for [`StateTransPattern`] types that also implement `Clone`.
@@ -298,6 +824,135 @@ pub trait StateTransPatternExtClone<S: State>: StateTransPattern<S> + Clone {
 
 impl<S: State, P: StateTransPattern<S> + Clone> StateTransPatternExtClone<S> for P {}
 
+/// An extension trait for [`StateTransPattern<S>`] when `S` is a [`LocalState`].
+///
+/// Systems configured by these methods take an `In<Vec<Entity>>` with the entities whose local
+/// state matched, and only run if there's at least one such entity.
+pub trait StateTransPatternExtLocal<S: LocalState>: StateTransPattern<S> {
+    /// Build a run condition that checks if any entity's `S` will undergo a matching transition
+    /// if triggered.
+    fn will_local_trans(self) -> impl 'static + Send + Sync + Fn(LocalFlushRef<S>) -> bool {
+        move |state| state.iter_trans(&self).next().is_some()
+    }
+
+    /// Configure a system to run with the entities whose `S` exits as part of a matching
+    /// transition.
+    fn on_local_exit<M>(
+        self,
+        system: impl IntoSystem<In<Vec<Entity>>, (), M>,
+    ) -> ScheduleConfigs<ScheduleSystem> {
+        local_trans_hook(self, system).in_set(ResolveStateSet::<S>::Exit)
+    }
+
+    /// Configure a system to run with the entities whose `S` undergoes a matching transition.
+    fn on_local_trans<M>(
+        self,
+        system: impl IntoSystem<In<Vec<Entity>>, (), M>,
+    ) -> ScheduleConfigs<ScheduleSystem> {
+        local_trans_hook(self, system).in_set(ResolveStateSet::<S>::Trans)
+    }
+
+    /// Configure a system to run with the entities whose `S` enters as part of a matching
+    /// transition.
+    fn on_local_enter<M>(
+        self,
+        system: impl IntoSystem<In<Vec<Entity>>, (), M>,
+    ) -> ScheduleConfigs<ScheduleSystem> {
+        local_trans_hook(self, system).in_set(ResolveStateSet::<S>::Enter)
+    }
+
+    /// Configure a guard system to run for each entity whose `S` is triggered to undergo a
+    /// matching transition.
+    ///
+    /// The guard system takes an `In<Entity>` and returns `true` to allow that entity's flush or
+    /// `false` to reject it. On rejection, the entity's next state will be reset to its current
+    /// state and a [`StateFlushRejected<S>`] event will be sent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use pyri_state::prelude::*;
+    /// #
+    /// # #[derive(State, Component, Clone, PartialEq, Eq)]
+    /// # #[state(local)]
+    /// # enum EnemyAi {
+    /// #     Idle,
+    /// #     Chase,
+    /// # }
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Stunned;
+    /// #
+    /// fn not_stunned(In(entity): In<Entity>, stunned: Query<(), With<Stunned>>) -> bool {
+    ///     !stunned.contains(entity)
+    /// }
+    ///
+    /// # fn plugin(app: &mut App) {
+    /// app.add_systems(StateFlush, (EnemyAi::Idle, EnemyAi::Chase).local_guard(not_stunned));
+    /// # }
+    /// ```
+    fn local_guard<M>(
+        self,
+        guard: impl IntoSystem<In<Entity>, bool, M>,
+    ) -> ScheduleConfigs<ScheduleSystem>
+    where
+        S: Clone + LocalState<Next: NextStateMut + Component<Mutability = Mutable>>,
+    {
+        local_guard(
+            self,
+            |state, pattern| state.iter_trans(pattern).collect(),
+            guard,
+        )
+    }
+}
+
+impl<S: LocalState, P: StateTransPattern<S>> StateTransPatternExtLocal<S> for P {}
+
+fn local_trans_hook<S: LocalState, P: StateTransPattern<S>, M>(
+    pattern: P,
+    system: impl IntoSystem<In<Vec<Entity>>, (), M>,
+) -> ScheduleConfigs<ScheduleSystem> {
+    let pattern = Arc::new(pattern);
+    local_hook(
+        pattern.clone(),
+        |state, pattern| state.iter_trans(pattern).collect(),
+        system,
+    )
+    .run_if(move |state: LocalFlushRef<S>| state.iter_trans(&*pattern).next().is_some())
+}
+
+// Pipe a guard system into a system that rejects the flush if the guard returns `false`.
+fn guard_flush<S: StateMut + Clone, M1, M2>(
+    guard: impl IntoSystem<(), bool, M1>,
+    condition: impl Condition<M2>,
+) -> ScheduleConfigs<ScheduleSystem> {
+    let guard = IntoSystem::into_system(guard);
+    let name = guard.name();
+    let reject_flush =
+        move |In(allow): In<bool>,
+              mut state: FlushMut<S>,
+              mut events: EventWriter<StateFlushRejected<S>>| {
+            if allow {
+                return;
+            }
+
+            let (old, new) = state.get();
+            events.write(StateFlushRejected {
+                guard: name.clone(),
+                entity: None,
+                old: old.cloned(),
+                new: new.cloned(),
+            });
+            state.reset();
+        };
+
+    guard
+        .pipe(reject_flush)
+        .run_if(S::is_triggered.and(condition))
+        .in_set(ResolveStateSet::<S>::Guard)
+}
+
 impl<S: State, P1: StatePattern<S>, P2: StatePattern<S>> StateTransPattern<S> for (P1, P2) {
     fn matches(&self, old: &S, new: &S) -> bool {
         self.0.matches(old) && self.1.matches(new)
@@ -379,6 +1034,131 @@ where
     }
 }
 
+/// A [`StatePattern`] or [`StateTransPattern`] that matches if either of two patterns match.
+///
+/// The usual way to construct this type is with [`StatePattern::or`] or
+/// [`StateTransPattern::or`]:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use pyri_state::prelude::*;
+/// #
+/// # #[derive(State, Clone, PartialEq, Eq)]
+/// # enum Screen {
+/// #     Title,
+/// #     Menu,
+/// #     Dialog,
+/// #     Gameplay,
+/// # }
+/// #
+/// const IN_MENU: Screen = Screen::Menu;
+/// const IN_DIALOG: Screen = Screen::Dialog;
+///
+/// # fn show_cursor() {}
+/// #
+/// # fn plugin(app: &mut App) {
+/// app.add_systems(StateFlush, IN_MENU.or(IN_DIALOG).on_enter(show_cursor));
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct OrPattern<P1, P2>(
+    /// The first pattern.
+    pub P1,
+    /// The second pattern.
+    pub P2,
+);
+
+impl<S: State, P1: StatePattern<S>, P2: StatePattern<S>> StatePattern<S> for OrPattern<P1, P2> {
+    fn matches(&self, state: &S) -> bool {
+        self.0.matches(state) || self.1.matches(state)
+    }
+}
+
+impl<S: State, P1: StateTransPattern<S>, P2: StateTransPattern<S>> StateTransPattern<S>
+    for OrPattern<P1, P2>
+{
+    fn matches(&self, old: &S, new: &S) -> bool {
+        self.0.matches(old, new) || self.1.matches(old, new)
+    }
+}
+
+/// A [`StatePattern`] or [`StateTransPattern`] that matches if both of two patterns match.
+///
+/// The usual way to construct this type is with [`StatePattern::and`] or
+/// [`StateTransPattern::and`], or with [`StatePattern::except`] or
+/// [`StateTransPattern::except`]:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use pyri_state::prelude::*;
+/// #
+/// # #[derive(State, Clone, PartialEq, Eq)]
+/// # struct Level(usize);
+/// #
+/// # fn spawn_boss() {}
+/// #
+/// # fn plugin(app: &mut App) {
+/// app.add_systems(StateFlush, Level::with(|x| x.0 % 5 == 0).except(Level(0)).on_enter(spawn_boss));
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct AndPattern<P1, P2>(
+    /// The first pattern.
+    pub P1,
+    /// The second pattern.
+    pub P2,
+);
+
+impl<S: State, P1: StatePattern<S>, P2: StatePattern<S>> StatePattern<S> for AndPattern<P1, P2> {
+    fn matches(&self, state: &S) -> bool {
+        self.0.matches(state) && self.1.matches(state)
+    }
+}
+
+impl<S: State, P1: StateTransPattern<S>, P2: StateTransPattern<S>> StateTransPattern<S>
+    for AndPattern<P1, P2>
+{
+    fn matches(&self, old: &S, new: &S) -> bool {
+        self.0.matches(old, new) && self.1.matches(old, new)
+    }
+}
+
+/// A [`StatePattern`] or [`StateTransPattern`] that matches if a pattern doesn't match.
+///
+/// The usual way to construct this type is with [`StatePattern::not`] or
+/// [`StateTransPattern::not`]:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use pyri_state::prelude::*;
+/// #
+/// # #[derive(State, Clone, PartialEq, Eq)]
+/// # struct Level(usize);
+/// #
+/// # fn play_level_music() {}
+/// #
+/// # fn plugin(app: &mut App) {
+/// app.add_systems(StateFlush, Level::ANY_TO_ANY.and(Level::when(|x, y| x == y).not()).on_enter(play_level_music));
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct NotPattern<P>(
+    /// The negated pattern.
+    pub P,
+);
+
+impl<S: State, P: StatePattern<S>> StatePattern<S> for NotPattern<P> {
+    fn matches(&self, state: &S) -> bool {
+        !self.0.matches(state)
+    }
+}
+
+impl<S: State, P: StateTransPattern<S>> StateTransPattern<S> for NotPattern<P> {
+    fn matches(&self, old: &S, new: &S) -> bool {
+        !self.0.matches(old, new)
+    }
+}
+
 /// A macro for building pattern-matching [`FnStatePattern`] and [`FnStateTransPattern`] instances.
 ///
 /// # Examples
@@ -7,13 +7,20 @@ pub use apply_flush::ApplyFlushSet;
 pub use resolve_state::ResolveStateSet;
 
 pub mod apply_flush;
+pub mod computed_state;
 pub mod detect_change;
 pub mod flush_event;
+pub mod flush_observer;
 pub mod resolve_state;
+pub mod sub_state;
+pub mod transition_table;
 
-use core::{fmt::Debug, hash::Hash};
+use core::{any::TypeId, fmt::Debug, hash::Hash};
 
-use bevy_ecs::schedule::ScheduleLabel;
+use bevy_ecs::{
+    resource::Resource,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+};
 
 /// The schedule that handles all [`State`](crate::state::State) flush logic, added after
 /// [`PreUpdate`](bevy_app::PreUpdate) by [`StatePlugin`](crate::setup::StatePlugin).
@@ -22,3 +29,32 @@ use bevy_ecs::schedule::ScheduleLabel;
 /// [`ApplyFlushSet`].
 #[derive(ScheduleLabel, Clone, Hash, PartialEq, Eq, Debug)]
 pub struct StateFlush;
+
+/// A schedule that runs the [`StateFlush`] schedule in the fixed timestep loop, added after
+/// [`FixedPreUpdate`](bevy_app::FixedPreUpdate) by
+/// [`StateFlushInPlugin`](crate::setup::StateFlushInPlugin).
+#[derive(ScheduleLabel, Clone, Hash, PartialEq, Eq, Debug)]
+pub struct FixedStateFlush;
+
+/// A resource that describes the current run of the [`StateFlush`] schedule.
+///
+/// Added by [`StatePlugin`](crate::setup::StatePlugin).
+#[derive(Resource, Clone, Debug)]
+pub struct CurrentStateFlush {
+    /// The schedule that is currently running the [`StateFlush`] schedule (or [`StateFlush`]
+    /// itself if it's running on its own).
+    pub schedule: InternedScheduleLabel,
+    /// The only [`State`](crate::state::State) type that can flush regardless of its flush
+    /// schedules, or `None` for all state types (e.g. set by
+    /// [`WorldExtState::flush_state`](crate::setup::WorldExtState::flush_state)).
+    pub state: Option<TypeId>,
+}
+
+impl Default for CurrentStateFlush {
+    fn default() -> Self {
+        Self {
+            schedule: StateFlush.intern(),
+            state: None,
+        }
+    }
+}
@@ -57,13 +57,14 @@ use core::{fmt::Debug, hash::Hash};
 
 use bevy_ecs::{
     entity::Entity,
-    schedule::{IntoScheduleConfigs as _, Schedule, SystemSet},
+    schedule::{Condition as _, IntoScheduleConfigs as _, Schedule, SystemSet},
     system::{Commands, Query, StaticSystemParam},
 };
 
 use crate::{
     access::{CurrentMut, NextRef},
     next_state::{NextState, TriggerStateFlush},
+    schedule::resolve_state::can_flush,
     state::{LocalState, State},
 };
 
@@ -95,7 +96,7 @@ fn apply_flush<S: State + Clone>(
 pub fn schedule_apply_flush<S: State + Clone>(schedule: &mut Schedule) {
     schedule.add_systems(
         (apply_flush::<S>, S::reset_trigger)
-            .run_if(S::is_triggered)
+            .run_if(S::is_triggered.and(can_flush::<S>))
             .in_set(ApplyFlushSet),
     );
 }
@@ -136,6 +137,7 @@ pub fn schedule_local_apply_flush<S: LocalState + Clone>(schedule: &mut Schedule
     schedule.add_systems(
         (local_apply_flush::<S>, local_reset_trigger::<S>)
             .chain()
+            .run_if(can_flush::<S>)
             .in_set(ApplyFlushSet),
     );
 }
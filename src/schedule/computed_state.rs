@@ -0,0 +1,205 @@
+//! Compute the next state from the next values of one or more source states.
+//!
+//! # Example
+//!
+//! Implement [`ComputedState`] and opt in to the [`ComputedStatePlugin`] by adding
+//! `#[state(computed)]`:
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use pyri_state::prelude::*;
+//! #
+//! # #[derive(State, Clone, PartialEq, Eq)]
+//! # enum Menu {
+//! #     Main,
+//! #     Pause,
+//! # }
+//! #
+//! # #[derive(State, Clone, PartialEq, Eq)]
+//! # enum Dialog {
+//! #     Open,
+//! #     Closed,
+//! # }
+//! #
+//! #[derive(State, Clone, PartialEq, Eq)]
+//! #[state(computed)]
+//! struct IsPaused;
+//!
+//! impl ComputedState for IsPaused {
+//!     type Sources = (Menu, Dialog);
+//!
+//!     fn compute((menu, dialog): (Option<&Menu>, Option<&Dialog>)) -> Option<Self> {
+//!         matches!((menu, dialog), (Some(Menu::Pause), _) | (_, Some(Dialog::Open)))
+//!             .then_some(IsPaused)
+//!     }
+//! }
+//! ```
+//!
+//! A computed state can't also be a [substate](super::sub_state), because both would set the
+//! next state in [`ResolveStateSet::Compute`]. The derive macro rejects the combination:
+//!
+//! ```compile_fail
+//! # use bevy::prelude::*;
+//! # use pyri_state::prelude::*;
+//! #
+//! # #[derive(State, Clone, PartialEq, Eq, Default)]
+//! # enum Menu {
+//! #     #[default]
+//! #     Main,
+//! #     Pause,
+//! # }
+//! #
+//! #[derive(State, Clone, PartialEq, Eq, Default)]
+//! #[state(computed, sub_of = Menu)]
+//! struct IsPaused;
+//! #
+//! # impl ComputedState for IsPaused {
+//! #     type Sources = Menu;
+//! #
+//! #     fn compute(menu: Option<&Menu>) -> Option<Self> {
+//! #         matches!(menu, Some(Menu::Pause)).then_some(IsPaused)
+//! #     }
+//! # }
+//! ```
+
+#[cfg(feature = "bevy_app")]
+pub use app::*;
+
+#[cfg(feature = "bevy_app")]
+mod app {
+    use core::marker::PhantomData;
+
+    use bevy_app::{App, Plugin};
+
+    use crate::schedule::StateFlush;
+
+    use super::{ComputedState, schedule_computed_state};
+
+    /// A plugin that adds a system to compute the [`ComputedState`] type `S` from its sources
+    /// to the [`StateFlush`] schedule.
+    ///
+    /// Calls [`schedule_computed_state<S>`].
+    pub struct ComputedStatePlugin<S: ComputedState>(PhantomData<S>);
+
+    impl<S: ComputedState> Plugin for ComputedStatePlugin<S> {
+        fn build(&self, app: &mut App) {
+            schedule_computed_state::<S>(app.get_schedule_mut(StateFlush).unwrap());
+        }
+    }
+
+    impl<S: ComputedState> Default for ComputedStatePlugin<S> {
+        fn default() -> Self {
+            Self(PhantomData)
+        }
+    }
+}
+
+use alloc::{vec, vec::Vec};
+
+use bevy_ecs::{
+    schedule::{InternedSystemSet, IntoScheduleConfigs as _, Schedule, SystemSet as _},
+    system::{ReadOnlySystemParam, StaticSystemParam, SystemParamItem},
+};
+
+use crate::{
+    access::{NextMut, NextRef},
+    schedule::ResolveStateSet,
+    state::{State, StateMut},
+};
+
+/// A [`State`] type whose next value is computed from the next values of its
+/// [`Sources`](Self::Sources).
+///
+/// The next state will be recomputed in [`ResolveStateSet::<Self>::Compute`] after all of its
+/// sources have been resolved. Nothing else should set the next state of a computed state, so
+/// it can't be combined with `sub_of`.
+pub trait ComputedState: StateMut {
+    /// The source [`State`] types, as a single `State` type or a tuple of `State` types.
+    type Sources: StateSources;
+
+    /// Compute the next state from the next values of the sources, or `None` to disable.
+    fn compute(sources: <Self::Sources as StateSources>::Item<'_>) -> Option<Self>;
+}
+
+/// A single [`State`] type or a tuple of `State` types that can be used as
+/// [`ComputedState::Sources`].
+pub trait StateSources: 'static + Send + Sync {
+    /// A [`ReadOnlySystemParam`] with access to the next values of the sources.
+    type Param: ReadOnlySystemParam;
+
+    /// The next values of the sources, or `None` if disabled.
+    type Item<'a>;
+
+    /// Get the next values of the sources from [`Self::Param`].
+    fn get<'a>(param: &'a SystemParamItem<Self::Param>) -> Self::Item<'a>;
+
+    /// The [`ResolveStateSet::Resolve`] system sets of the sources.
+    fn resolve_sets() -> Vec<InternedSystemSet>;
+}
+
+impl<S: State> StateSources for S {
+    type Param = NextRef<'static, 'static, S>;
+
+    type Item<'a> = Option<&'a S>;
+
+    fn get<'a>(param: &'a SystemParamItem<Self::Param>) -> Self::Item<'a> {
+        param.get()
+    }
+
+    fn resolve_sets() -> Vec<InternedSystemSet> {
+        vec![ResolveStateSet::<S>::Resolve.intern()]
+    }
+}
+
+macro_rules! impl_state_sources {
+    ($(($S:ident, $i:tt)),*) => {
+        impl<$($S: State),*> StateSources for ($($S,)*) {
+            type Param = ($(NextRef<'static, 'static, $S>,)*);
+
+            type Item<'a> = ($(Option<&'a $S>,)*);
+
+            fn get<'a>(param: &'a SystemParamItem<Self::Param>) -> Self::Item<'a> {
+                ($(param.$i.get(),)*)
+            }
+
+            fn resolve_sets() -> Vec<InternedSystemSet> {
+                vec![$(ResolveStateSet::<$S>::Resolve.intern()),*]
+            }
+        }
+    };
+}
+
+impl_state_sources!((A, 0));
+impl_state_sources!((A, 0), (B, 1));
+impl_state_sources!((A, 0), (B, 1), (C, 2));
+impl_state_sources!((A, 0), (B, 1), (C, 2), (D, 3));
+impl_state_sources!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4));
+impl_state_sources!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5));
+impl_state_sources!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6));
+impl_state_sources!(
+    (A, 0),
+    (B, 1),
+    (C, 2),
+    (D, 3),
+    (E, 4),
+    (F, 5),
+    (G, 6),
+    (H, 7)
+);
+
+fn compute_state<S: ComputedState>(
+    sources: StaticSystemParam<<S::Sources as StateSources>::Param>,
+    mut state: NextMut<S>,
+) {
+    state.set(S::compute(S::Sources::get(&sources)));
+}
+
+/// Add a system to compute the [`ComputedState`] type `S` from its sources to a schedule.
+///
+/// Used in [`ComputedStatePlugin<S>`].
+pub fn schedule_computed_state<S: ComputedState>(schedule: &mut Schedule) {
+    for system_set in S::Sources::resolve_sets() {
+        schedule.configure_sets(ResolveStateSet::<S>::Resolve.after(system_set));
+    }
+    schedule.add_systems(compute_state::<S>.in_set(ResolveStateSet::<S>::Compute));
+}
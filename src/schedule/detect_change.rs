@@ -11,10 +11,11 @@ mod app {
 
     use crate::{
         schedule::StateFlush,
-        state::{LocalState, State},
+        setup::add_pending_flush_condition,
+        state::{LocalState, State, StateExtEq as _},
     };
 
-    use super::{schedule_detect_change, schedule_local_detect_change};
+    use super::{local_will_change, schedule_detect_change, schedule_local_detect_change};
 
     /// A plugin that adds a change detection system for the [`State`] type `S`
     /// to the [`StateFlush`] schedule.
@@ -24,6 +25,7 @@ mod app {
 
     impl<S: State + Eq> Plugin for DetectChangePlugin<S> {
         fn build(&self, app: &mut App) {
+            add_pending_flush_condition::<S, _>(app, S::will_change);
             schedule_detect_change::<S>(app.get_schedule_mut(StateFlush).unwrap());
         }
     }
@@ -42,6 +44,7 @@ mod app {
 
     impl<S: LocalState + Eq> Plugin for LocalDetectChangePlugin<S> {
         fn build(&self, app: &mut App) {
+            add_pending_flush_condition::<S, _>(app, local_will_change::<S>);
             schedule_local_detect_change::<S>(app.get_schedule_mut(StateFlush).unwrap());
         }
     }
@@ -87,6 +90,16 @@ fn local_detect_change<S: LocalState + Eq>(
     }
 }
 
+/// A run condition that checks if any local `S` will change if triggered.
+fn local_will_change<S: LocalState + Eq>(
+    next_param: StaticSystemParam<<S::Next as NextState>::Param>,
+    state_query: Query<(Option<&S>, &S::Next)>,
+) -> bool {
+    state_query
+        .iter()
+        .any(|(current, next)| current != next.next_state(&next_param))
+}
+
 /// Add local change detection systems for the [`State`] type `S` to a schedule.
 ///
 /// Used in [`LocalDetectChangePlugin<S>`].
@@ -0,0 +1,247 @@
+//! Trigger [`OnStateExit`], [`OnStateTrans`], and [`OnStateEnter`] observers on state flush.
+//!
+//! Unlike [`StateFlushEvent`](super::flush_event::StateFlushEvent), observers run immediately
+//! within the [`StateFlush`](crate::schedule::StateFlush) schedule, from an exclusive system in
+//! the corresponding [`ResolveStateSet`]. For local states, the observers target the entity
+//! with the local state, so they can be attached per entity.
+//!
+//! # Example
+//!
+//! Opt in to the [`FlushObserverPlugin`] for `Screen` by adding `#[state(flush_observer)]`:
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use pyri_state::{prelude::*, schedule::flush_observer::OnStateEnter};
+//! #
+//! #[derive(State, Clone, PartialEq, Eq, Debug)]
+//! #[state(flush_observer)]
+//! enum Screen {
+//!     Title,
+//!     Gameplay,
+//! }
+//!
+//! fn log_enter(trigger: Trigger<OnStateEnter<Screen>>) {
+//!     info!("Entered {:?}", trigger.0);
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(StatePlugin)
+//!     .add_state::<Screen>()
+//!     .add_observer(log_enter);
+//! ```
+
+#[cfg(feature = "bevy_app")]
+pub use app::*;
+
+#[cfg(feature = "bevy_app")]
+mod app {
+    use core::marker::PhantomData;
+
+    use bevy_app::{App, Plugin};
+
+    use crate::{
+        schedule::StateFlush,
+        state::{LocalState, State},
+    };
+
+    use super::{schedule_flush_observer, schedule_local_flush_observer};
+
+    /// A plugin that adds observer triggering systems for the [`State`] type `S` to the
+    /// [`StateFlush`] schedule.
+    ///
+    /// Calls [`schedule_flush_observer<S>`].
+    pub struct FlushObserverPlugin<S: State + Clone>(PhantomData<S>);
+
+    impl<S: State + Clone> Plugin for FlushObserverPlugin<S> {
+        fn build(&self, app: &mut App) {
+            schedule_flush_observer::<S>(app.get_schedule_mut(StateFlush).unwrap());
+        }
+    }
+
+    impl<S: State + Clone> Default for FlushObserverPlugin<S> {
+        fn default() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    /// A plugin that adds local observer triggering systems for the [`State`] type `S` to the
+    /// [`StateFlush`] schedule.
+    ///
+    /// Calls [`schedule_local_flush_observer<S>`].
+    pub struct LocalFlushObserverPlugin<S: LocalState + Clone>(PhantomData<S>);
+
+    impl<S: LocalState + Clone> Plugin for LocalFlushObserverPlugin<S> {
+        fn build(&self, app: &mut App) {
+            schedule_local_flush_observer::<S>(app.get_schedule_mut(StateFlush).unwrap());
+        }
+    }
+
+    impl<S: LocalState + Clone> Default for LocalFlushObserverPlugin<S> {
+        fn default() -> Self {
+            Self(PhantomData)
+        }
+    }
+}
+
+use alloc::vec::Vec;
+
+use bevy_ecs::{
+    entity::Entity,
+    event::Event,
+    schedule::{IntoScheduleConfigs as _, Schedule},
+    system::{Query, StaticSystemParam, SystemState},
+    world::World,
+};
+
+use crate::{
+    access::FlushRef,
+    next_state::{NextState, TriggerStateFlush},
+    schedule::ResolveStateSet,
+    state::{LocalState, State},
+};
+
+/// An observer event triggered when the [`State`] type `S` exits.
+///
+/// Targets the entity for a local state. Added by [`FlushObserverPlugin<S>`] and
+/// [`LocalFlushObserverPlugin<S>`].
+#[derive(Event, Clone, Debug)]
+pub struct OnStateExit<S: State>(
+    /// The state being exited.
+    pub S,
+);
+
+/// An observer event triggered when the [`State`] type `S` transitions.
+///
+/// Targets the entity for a local state. Added by [`FlushObserverPlugin<S>`] and
+/// [`LocalFlushObserverPlugin<S>`].
+#[derive(Event, Clone, Debug)]
+pub struct OnStateTrans<S: State> {
+    /// The state before the transition.
+    pub old: S,
+    /// The state after the transition.
+    pub new: S,
+}
+
+/// An observer event triggered when the [`State`] type `S` enters.
+///
+/// Targets the entity for a local state. Added by [`FlushObserverPlugin<S>`] and
+/// [`LocalFlushObserverPlugin<S>`].
+#[derive(Event, Clone, Debug)]
+pub struct OnStateEnter<S: State>(
+    /// The state being entered.
+    pub S,
+);
+
+fn trigger_state_exit<S: State + Clone>(world: &mut World, state: &mut SystemState<FlushRef<S>>) {
+    let old = state.get(world).current.unwrap().clone();
+    world.trigger(OnStateExit(old));
+}
+
+fn trigger_state_trans<S: State + Clone>(world: &mut World, state: &mut SystemState<FlushRef<S>>) {
+    let event = {
+        let state = state.get(world);
+        let (old, new) = state.unwrap();
+        OnStateTrans {
+            old: old.clone(),
+            new: new.clone(),
+        }
+    };
+    world.trigger(event);
+}
+
+fn trigger_state_enter<S: State + Clone>(world: &mut World, state: &mut SystemState<FlushRef<S>>) {
+    let new = state.get(world).next.unwrap().clone();
+    world.trigger(OnStateEnter(new));
+}
+
+/// Add observer triggering systems for the [`State`] type `S` to a schedule.
+///
+/// Used in [`FlushObserverPlugin<S>`].
+pub fn schedule_flush_observer<S: State + Clone>(schedule: &mut Schedule) {
+    schedule.add_systems((
+        trigger_state_exit::<S>.in_set(ResolveStateSet::<S>::AnyExit),
+        trigger_state_trans::<S>.in_set(ResolveStateSet::<S>::AnyTrans),
+        trigger_state_enter::<S>.in_set(ResolveStateSet::<S>::AnyEnter),
+    ));
+}
+
+fn trigger_local_state_exit<S: LocalState + Clone>(
+    world: &mut World,
+    state: &mut SystemState<Query<(Entity, &S, &TriggerStateFlush<S>)>>,
+) {
+    let events = state
+        .get(world)
+        .iter()
+        .filter(|(_, _, trigger)| trigger.0)
+        .map(|(entity, old, _)| (entity, OnStateExit(old.clone())))
+        .collect::<Vec<_>>();
+
+    for (entity, event) in events {
+        world.trigger_targets(event, entity);
+    }
+}
+
+fn trigger_local_state_trans<S: LocalState + Clone>(
+    world: &mut World,
+    state: &mut SystemState<(
+        StaticSystemParam<<S::Next as NextState>::Param>,
+        Query<(Entity, &S, &S::Next, &TriggerStateFlush<S>)>,
+    )>,
+) {
+    let events = {
+        let (next_param, state_query) = state.get(world);
+        state_query
+            .iter()
+            .filter(|(_, _, _, trigger)| trigger.0)
+            .filter_map(|(entity, old, new, _)| {
+                let new = new.next_state(&next_param)?;
+                Some((
+                    entity,
+                    OnStateTrans {
+                        old: old.clone(),
+                        new: new.clone(),
+                    },
+                ))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for (entity, event) in events {
+        world.trigger_targets(event, entity);
+    }
+}
+
+fn trigger_local_state_enter<S: LocalState + Clone>(
+    world: &mut World,
+    state: &mut SystemState<(
+        StaticSystemParam<<S::Next as NextState>::Param>,
+        Query<(Entity, &S::Next, &TriggerStateFlush<S>)>,
+    )>,
+) {
+    let events = {
+        let (next_param, state_query) = state.get(world);
+        state_query
+            .iter()
+            .filter(|(_, _, trigger)| trigger.0)
+            .filter_map(|(entity, new, _)| {
+                let new = new.next_state(&next_param)?;
+                Some((entity, OnStateEnter(new.clone())))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for (entity, event) in events {
+        world.trigger_targets(event, entity);
+    }
+}
+
+/// Add local observer triggering systems for the [`State`] type `S` to a schedule.
+///
+/// Used in [`LocalFlushObserverPlugin<S>`].
+pub fn schedule_local_flush_observer<S: LocalState + Clone>(schedule: &mut Schedule) {
+    schedule.add_systems((
+        trigger_local_state_exit::<S>.in_set(ResolveStateSet::<S>::Exit),
+        trigger_local_state_trans::<S>.in_set(ResolveStateSet::<S>::Trans),
+        trigger_local_state_enter::<S>.in_set(ResolveStateSet::<S>::Enter),
+    ));
+}
@@ -9,11 +9,14 @@ mod app {
     use core::marker::PhantomData;
 
     use bevy_app::{App, Plugin};
-    use bevy_ecs::schedule::{InternedSystemSet, SystemSet};
+    use bevy_ecs::schedule::{InternedScheduleLabel, InternedSystemSet, ScheduleLabel, SystemSet};
 
-    use crate::state::State;
+    use crate::{setup::add_pending_flush_condition, state::State};
 
-    use super::{ResolveStateSet, schedule_resolve_state};
+    use super::{
+        ResolveStateSet, StateFlushRejected, StateFlushSchedules, is_any_triggered,
+        schedule_resolve_state,
+    };
 
     /// A plugin that configures the [`ResolveStateSet<S>`] system sets for the [`State`]
     /// type `S` in the [`StateFlush`](crate::schedule::StateFlush) schedule.
@@ -21,15 +24,24 @@ mod app {
     /// To specify a dependency relative to another `State` type `T`, add
     /// [`ResolveStateSet::<T>::Resolve`] to [`after`](Self::after) or [`before`](Self::before).
     ///
+    /// To only flush `S` in specific [flush schedules](crate::setup::StateFlushInPlugin),
+    /// add them to [`flush_in`](Self::flush_in).
+    ///
     /// Calls [`schedule_resolve_state<S>`].
     pub struct ResolveStatePlugin<S: State> {
         after: Vec<InternedSystemSet>,
         before: Vec<InternedSystemSet>,
+        flush_in: Vec<InternedScheduleLabel>,
         _phantom: PhantomData<S>,
     }
 
     impl<S: State> Plugin for ResolveStatePlugin<S> {
         fn build(&self, app: &mut App) {
+            if !self.flush_in.is_empty() {
+                app.insert_resource(StateFlushSchedules::<S>(self.flush_in.clone(), PhantomData));
+            }
+            app.add_event::<StateFlushRejected<S>>();
+            add_pending_flush_condition::<S, _>(app, is_any_triggered::<S>);
             schedule_resolve_state::<S>(
                 app.get_schedule_mut(crate::schedule::StateFlush).unwrap(),
                 &self.after,
@@ -43,6 +55,7 @@ mod app {
             Self {
                 after: Vec::new(),
                 before: Vec::new(),
+                flush_in: Vec::new(),
                 _phantom: PhantomData,
             }
         }
@@ -54,6 +67,7 @@ mod app {
             Self {
                 after,
                 before,
+                flush_in: Vec::new(),
                 _phantom: PhantomData,
             }
         }
@@ -69,16 +83,35 @@ mod app {
             self.before.push(ResolveStateSet::<T>::Resolve.intern());
             self
         }
+
+        /// Only flush in a specific flush schedule (e.g. [`StateFlush`](crate::schedule::StateFlush)
+        /// or [`FixedStateFlush`](crate::schedule::FixedStateFlush)).
+        ///
+        /// By default, `S` will flush in every flush schedule.
+        pub fn flush_in(mut self, schedule: impl ScheduleLabel) -> Self {
+            self.flush_in.push(schedule.intern());
+            self
+        }
     }
 }
 
-use core::{convert::Infallible, fmt::Debug, hash::Hash, marker::PhantomData};
+use alloc::{borrow::Cow, vec::Vec};
+use core::{any::TypeId, convert::Infallible, fmt::Debug, hash::Hash, marker::PhantomData};
 
-use bevy_ecs::schedule::{
-    Condition, InternedSystemSet, IntoScheduleConfigs as _, Schedule, SystemSet,
+use bevy_ecs::{
+    entity::Entity,
+    event::Event,
+    resource::Resource,
+    schedule::InternedScheduleLabel,
+    schedule::{Condition, InternedSystemSet, IntoScheduleConfigs as _, Schedule, SystemSet},
+    system::{Query, Res},
 };
 
-use crate::{schedule::ApplyFlushSet, state::State};
+use crate::{
+    next_state::TriggerStateFlush,
+    schedule::{ApplyFlushSet, CurrentStateFlush},
+    state::State,
+};
 
 /// A suite of system sets in the [`StateFlush`](crate::schedule::StateFlush)
 /// schedule for each [`State`] type `S`.
@@ -90,7 +123,8 @@ use crate::{schedule::ApplyFlushSet, state::State};
 ///    state dependencies, and before [`ApplyFlushSet`])
 ///     1. [`Compute`](Self::Compute)
 ///     2. [`Trigger`](Self::Trigger)
-///     3. [`Flush`](Self::Flush) (and [`AnyFlush`](Self::AnyFlush) if the global state will flush)
+///     3. [`Guard`](Self::Guard)
+///     4. [`Flush`](Self::Flush) (and [`AnyFlush`](Self::AnyFlush) if the global state will flush)
 ///         1. [`Exit`](Self::Exit) (and [`AnyExit`](Self::AnyExit) if the global state will exit)
 ///         2. [`Trans`](Self::Trans) (and [`AnyTrans`](Self::AnyTrans) if the global state will
 ///            transition)
@@ -104,6 +138,8 @@ pub enum ResolveStateSet<S: State> {
     Compute,
     /// Decide whether to trigger a flush for `S`.
     Trigger,
+    /// Run flush guards for `S` that can reject or rewrite a triggered flush.
+    Guard,
     /// Run on-flush hooks for `S`.
     Flush,
     /// Run on-exit hooks for `S`.
@@ -130,6 +166,7 @@ impl<S: State> Clone for ResolveStateSet<S> {
             Self::Resolve => Self::Resolve,
             Self::Compute => Self::Compute,
             Self::Trigger => Self::Trigger,
+            Self::Guard => Self::Guard,
             Self::Flush => Self::Flush,
             Self::Exit => Self::Exit,
             Self::Trans => Self::Trans,
@@ -163,6 +200,7 @@ impl<S: State> Debug for ResolveStateSet<S> {
             Self::Resolve => write!(f, "Resolve"),
             Self::Compute => write!(f, "Compute"),
             Self::Trigger => write!(f, "Trigger"),
+            Self::Guard => write!(f, "Guard"),
             Self::Flush => write!(f, "Flush"),
             Self::Exit => write!(f, "Exit"),
             Self::Trans => write!(f, "Trans"),
@@ -176,6 +214,58 @@ impl<S: State> Debug for ResolveStateSet<S> {
     }
 }
 
+/// An event sent whenever a flush of the [`State`] type `S` is rejected by a
+/// [guard](crate::pattern::StateTransPattern::guard).
+///
+/// Added by [`ResolveStatePlugin<S>`].
+#[derive(Event)]
+pub struct StateFlushRejected<S: State> {
+    /// The name of the guard system that rejected the flush.
+    pub guard: Cow<'static, str>,
+    /// The entity whose local state flush was rejected, or `None` for the global state.
+    pub entity: Option<Entity>,
+    /// The state before the rejected flush, or `None` if disabled.
+    pub old: Option<S>,
+    /// The state after the rejected flush, or `None` if disabled.
+    pub new: Option<S>,
+}
+
+/// A resource that lists the flush schedules that the [`State`] type `S` can flush in, or all
+/// flush schedules if absent.
+///
+/// Added by [`ResolveStatePlugin::flush_in`].
+#[derive(Resource)]
+pub struct StateFlushSchedules<S: State>(
+    /// The flush schedules (e.g. [`StateFlush`](crate::schedule::StateFlush)).
+    pub Vec<InternedScheduleLabel>,
+    PhantomData<S>,
+);
+
+/// A run condition that checks if the [`State`] type `S` can flush in the current run of the
+/// [`StateFlush`](crate::schedule::StateFlush) schedule, as described by [`CurrentStateFlush`]
+/// and configured by [`ResolveStatePlugin::flush_in`].
+pub fn can_flush<S: State>(
+    current: Option<Res<CurrentStateFlush>>,
+    schedules: Option<Res<StateFlushSchedules<S>>>,
+) -> bool {
+    let Some(current) = current else {
+        return true;
+    };
+
+    match current.state {
+        Some(state) => state == TypeId::of::<S>(),
+        None => schedules.is_none_or(|x| x.0.contains(&current.schedule)),
+    }
+}
+
+/// A run condition that checks if the global or any local `S` is triggered to flush.
+fn is_any_triggered<S: State>(
+    trigger: Option<Res<TriggerStateFlush<S>>>,
+    local_query: Query<&TriggerStateFlush<S>>,
+) -> bool {
+    trigger.is_some_and(|x| x.0) || local_query.iter().any(|x| x.0)
+}
+
 /// Configure [`ResolveStateSet<S>`] for the [`State`] type `S` in a schedule.
 ///
 /// To specify a dependency relative to another `State` type `T`, include
@@ -197,12 +287,16 @@ pub fn schedule_resolve_state<S: State>(
 
     // Internal ordering
     schedule.configure_sets((
-        ResolveStateSet::<S>::Resolve.before(ApplyFlushSet),
+        ResolveStateSet::<S>::Resolve
+            .before(ApplyFlushSet)
+            .run_if(can_flush::<S>),
         (
             ResolveStateSet::<S>::Compute,
             // Logic in this system set should only run if not triggered.
             ResolveStateSet::<S>::Trigger,
             // Logic in this system set should only run if triggered.
+            ResolveStateSet::<S>::Guard,
+            // Logic in this system set should only run if triggered.
             ResolveStateSet::<S>::Flush,
         )
             .chain()
@@ -0,0 +1,154 @@
+//! Enable and disable a substate while its parent state matches a pattern.
+//!
+//! # Example
+//!
+//! Opt in to the [`SubStatePlugin`] for `Level` by adding `#[state(sub_of = ..., when = ...)]`:
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use pyri_state::prelude::*;
+//! #
+//! #[derive(State, Clone, PartialEq, Eq, Default)]
+//! enum Screen {
+//!     #[default]
+//!     Title,
+//!     Gameplay,
+//! }
+//!
+//! // `Level` will be enabled with its default value on entering `Screen::Gameplay`,
+//! // and disabled on exiting `Screen::Gameplay`.
+//! #[derive(State, Clone, PartialEq, Eq, Default)]
+//! #[state(sub_of = Screen, when = Screen::Gameplay)]
+//! struct Level(usize);
+//! ```
+
+#[cfg(feature = "bevy_app")]
+pub use app::*;
+
+#[cfg(feature = "bevy_app")]
+mod app {
+    use core::marker::PhantomData;
+
+    use bevy_app::{App, Plugin};
+
+    use crate::{
+        pattern::{AnyStatePattern, StatePattern},
+        schedule::StateFlush,
+        state::{State, StateMut},
+    };
+
+    use super::schedule_sub_state;
+
+    /// A plugin that enables the [`State`] type `S` while its parent state `T` matches a
+    /// [`StatePattern`], and disables it otherwise.
+    ///
+    /// Calls [`schedule_sub_state<S, T, P>`].
+    pub struct SubStatePlugin<S, T, P = AnyStatePattern<T>>
+    where
+        S: StateMut + Clone + Default,
+        T: State,
+        P: StatePattern<T> + Clone,
+    {
+        pattern: P,
+        remember: bool,
+        _phantom: PhantomData<(S, T)>,
+    }
+
+    impl<S, T, P> Plugin for SubStatePlugin<S, T, P>
+    where
+        S: StateMut + Clone + Default,
+        T: State,
+        P: StatePattern<T> + Clone,
+    {
+        fn build(&self, app: &mut App) {
+            schedule_sub_state::<S, T, P>(
+                app.get_schedule_mut(StateFlush).unwrap(),
+                self.pattern.clone(),
+                self.remember,
+            );
+        }
+    }
+
+    impl<S, T> Default for SubStatePlugin<S, T>
+    where
+        S: StateMut + Clone + Default,
+        T: State,
+    {
+        fn default() -> Self {
+            Self::new(T::ANY)
+        }
+    }
+
+    impl<S, T, P> SubStatePlugin<S, T, P>
+    where
+        S: StateMut + Clone + Default,
+        T: State,
+        P: StatePattern<T> + Clone,
+    {
+        /// Create a [`SubStatePlugin`] that enables `S` while `T` matches a specific pattern.
+        pub fn new(pattern: P) -> Self {
+            Self {
+                pattern,
+                remember: false,
+                _phantom: PhantomData,
+            }
+        }
+
+        /// Re-enable `S` with its last value instead of its default value.
+        pub fn remember(mut self) -> Self {
+            self.remember = true;
+            self
+        }
+    }
+}
+
+use bevy_ecs::{
+    schedule::{IntoScheduleConfigs as _, Schedule},
+    system::Local,
+};
+
+use crate::{
+    access::{FlushMut, FlushRef},
+    pattern::StatePattern,
+    schedule::ResolveStateSet,
+    state::{State, StateMut},
+};
+
+/// Add a system that enables the [`State`] type `S` while its parent state `T` matches a
+/// [`StatePattern`] to a schedule.
+///
+/// If `remember` is true, `S` will be re-enabled with its last value instead of its default
+/// value.
+///
+/// Used in [`SubStatePlugin<S, T, P>`].
+pub fn schedule_sub_state<S, T, P>(schedule: &mut Schedule, pattern: P, remember: bool)
+where
+    S: StateMut + Clone + Default,
+    T: State,
+    P: StatePattern<T>,
+{
+    let compute_sub_state =
+        move |parent: FlushRef<T>, mut state: FlushMut<S>, mut memory: Local<Option<S>>| {
+            let parent = if parent.next.is_triggered() {
+                parent.next.get()
+            } else {
+                parent.current.get()
+            };
+
+            if matches!(parent, Some(x) if pattern.matches(x)) {
+                if state.next.will_be_disabled() {
+                    state.enter(memory.take().unwrap_or_default());
+                    state.trigger();
+                }
+            } else if state.next.will_be_enabled() {
+                if remember {
+                    *memory = state.current.get().cloned();
+                }
+                state.disable();
+                state.trigger();
+            }
+        };
+
+    schedule.configure_sets(ResolveStateSet::<S>::Resolve.after(ResolveStateSet::<T>::Resolve));
+    schedule.add_systems(compute_sub_state.in_set(ResolveStateSet::<S>::Compute));
+}
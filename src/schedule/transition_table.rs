@@ -0,0 +1,120 @@
+//! Reject any transition that isn't listed in a transition table.
+//!
+//! # Example
+//!
+//! Implement [`TransitionTable`] and opt in to the [`TransitionTablePlugin`] by adding
+//! `#[state(transitions(...))]`:
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use pyri_state::prelude::*;
+//! #
+//! #[derive(State, Clone, PartialEq, Eq, Debug)]
+//! #[state(log_flush, transitions(
+//!     Title => Loading,
+//!     Loading => Gameplay,
+//!     Gameplay => Title | Gameplay,
+//! ))]
+//! enum Screen {
+//!     Title,
+//!     Loading,
+//!     Gameplay,
+//! }
+//! ```
+//!
+//! Each side of a transition is a pattern, and bare variant names (e.g. `Title`) are
+//! prefixed with `Self::` for an enum. A struct state must use `Self` in its patterns instead:
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use pyri_state::prelude::*;
+//! #
+//! #[derive(State, Clone, PartialEq, Eq, Debug)]
+//! #[state(transitions(Self(0) => Self(1), Self(1) => Self(0) | Self(2)))]
+//! struct Level(usize);
+//! ```
+//!
+//! The derive macro rejects the type name in a struct state's patterns:
+//!
+//! ```compile_fail
+//! # use bevy::prelude::*;
+//! # use pyri_state::prelude::*;
+//! #
+//! #[derive(State, Clone, PartialEq, Eq, Debug)]
+//! #[state(transitions(Level(0) => Level(1)))]
+//! struct Level(usize);
+//! ```
+//!
+//! Enabling and disabling the state are always allowed. A rejected flush will send a
+//! [`StateFlushRejected`](super::resolve_state::StateFlushRejected) event. The event is only
+//! logged if the state opts in to `log_flush` and
+//! [`StateDebugSettings::log_reject`](crate::debug::StateDebugSettings::log_reject) is enabled.
+
+#[cfg(feature = "bevy_app")]
+pub use app::*;
+
+#[cfg(feature = "bevy_app")]
+mod app {
+    use core::marker::PhantomData;
+
+    use bevy_app::{App, Plugin};
+
+    use crate::schedule::StateFlush;
+
+    use super::{TransitionTable, schedule_transition_table};
+
+    /// A plugin that adds a guard system to reject any transition not listed in the
+    /// [`TransitionTable`] for the [`State`](crate::state::State) type `S` to the
+    /// [`StateFlush`] schedule.
+    ///
+    /// Calls [`schedule_transition_table<S>`].
+    pub struct TransitionTablePlugin<S: TransitionTable>(PhantomData<S>);
+
+    impl<S: TransitionTable> Plugin for TransitionTablePlugin<S> {
+        fn build(&self, app: &mut App) {
+            schedule_transition_table::<S>(app.get_schedule_mut(StateFlush).unwrap());
+        }
+    }
+
+    impl<S: TransitionTable> Default for TransitionTablePlugin<S> {
+        fn default() -> Self {
+            Self(PhantomData)
+        }
+    }
+}
+
+use bevy_ecs::schedule::Schedule;
+
+use crate::{access::FlushRef, pattern::StateTransPattern as _, state::StateMut};
+
+/// A [`State`](crate::state::State) type with a table of allowed transitions.
+///
+/// This trait can be implemented with `#[state(transitions(...))]` in the
+/// [derive macro](pyri_state_derive::State).
+pub trait TransitionTable: StateMut + Clone {
+    /// Check if the transition from `old` to `new` is allowed.
+    fn allows(old: &Self, new: &Self) -> bool;
+
+    /// The allowed transitions as `(old, new)` pairs of human-readable patterns, for
+    /// documentation purposes (e.g. in a [`StateDiagram`](crate::debug::diagram::StateDiagram)).
+    fn transitions() -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+}
+
+// An earlier guard may have rewritten or disabled the next state, so only check transitions
+// between enabled states.
+fn allow_transition<S: TransitionTable>(state: FlushRef<S>) -> bool {
+    match state.get() {
+        (Some(old), Some(new)) => S::allows(old, new),
+        _ => true,
+    }
+}
+
+/// Add a guard system to reject any transition not listed in the [`TransitionTable`] for the
+/// [`State`](crate::state::State) type `S` to a schedule.
+///
+/// Used in [`TransitionTablePlugin<S>`].
+pub fn schedule_transition_table<S: TransitionTable>(schedule: &mut Schedule) {
+    schedule.add_systems(S::ANY_TO_ANY.guard(allow_transition::<S>));
+}
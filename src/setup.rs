@@ -5,17 +5,35 @@
 
 #[cfg(feature = "bevy_app")]
 pub use app::*;
+
+use core::any::TypeId;
+
 use bevy_ecs::{
-    system::{Commands, EntityCommands},
+    component::{Component, Mutable},
+    system::{Commands, EntityCommands, Query, StaticSystemParam, SystemState},
     world::{EntityWorldMut, FromWorld, World},
 };
 
 #[cfg(feature = "bevy_app")]
 mod app {
-    use bevy_app::{App, MainScheduleOrder, Plugin, PreUpdate};
-    use bevy_ecs::world::FromWorld;
-
-    use crate::{next_state::NextState, schedule::StateFlush, state::State};
+    use alloc::{boxed::Box, vec::Vec};
+
+    use bevy_app::{
+        App, FixedMainScheduleOrder, FixedPreUpdate, MainScheduleOrder, Plugin, PreUpdate,
+    };
+    use bevy_ecs::{
+        change_detection::Mut,
+        resource::Resource,
+        schedule::{BoxedCondition, Condition, InternedScheduleLabel, ScheduleLabel},
+        system::{IntoSystem, System as _},
+        world::{FromWorld, World},
+    };
+
+    use crate::{
+        next_state::NextState,
+        schedule::{CurrentStateFlush, FixedStateFlush, StateFlush, resolve_state::can_flush},
+        state::State,
+    };
 
     use super::{insert_state, state_exists};
 
@@ -24,6 +42,9 @@ mod app {
     /// - Adds the [`StateFlush`] schedule to the [`MainScheduleOrder`] after [`PreUpdate`].
     /// - Adds the [`bevy_state` plugin](bevy_state::app::StatesPlugin) if the
     ///   `bevy_state` feature is enabled.
+    ///
+    /// To configure how states flush, add a [`StateSettlePlugin`] or [`StateFlushInPlugin`] after
+    /// this plugin.
     pub struct StatePlugin;
 
     impl Plugin for StatePlugin {
@@ -33,13 +54,194 @@ mod app {
             app.add_plugins(bevy_state::app::StatesPlugin);
 
             // Add the `StateFlush` schedule.
-            app.init_schedule(StateFlush)
+            app.init_resource::<CurrentStateFlush>()
+                .init_resource::<PendingStateFlush>()
+                .init_schedule(StateFlush)
                 .world_mut()
                 .resource_mut::<MainScheduleOrder>()
                 .insert_after(PreUpdate, StateFlush);
         }
     }
 
+    /// A plugin that enables settle mode, re-running the [`StateFlush`] schedule up to
+    /// `max_flushes` times per frame until no state is pending a flush.
+    ///
+    /// By default, the `StateFlush` schedule runs once per frame, so a chain of dependent state
+    /// changes can take several frames to resolve. In settle mode, the `StateFlush` schedule
+    /// re-runs while any state is still triggered to flush (or will change with
+    /// [`DetectChangePlugin`](crate::schedule::detect_change::DetectChangePlugin)). A warning
+    /// will be logged if states are still pending a flush after `max_flushes` flushes (requires
+    /// the `debug` feature).
+    ///
+    /// Must be added after [`StatePlugin`], but can be added before or after any `State` types.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use pyri_state::prelude::*;
+    /// #
+    /// let mut app = App::new();
+    /// app.add_plugins((StatePlugin, StateSettlePlugin::new(8)));
+    /// ```
+    pub struct StateSettlePlugin {
+        max_flushes: usize,
+    }
+
+    impl Plugin for StateSettlePlugin {
+        fn build(&self, app: &mut App) {
+            app.insert_resource(StateSettle(self.max_flushes))
+                .add_systems(SettleStateFlush, settle_state_flush)
+                .world_mut()
+                .resource_mut::<MainScheduleOrder>()
+                .insert_after(StateFlush, SettleStateFlush);
+        }
+    }
+
+    impl StateSettlePlugin {
+        /// Create a `StateSettlePlugin` that flushes up to `max_flushes` times per frame.
+        pub fn new(max_flushes: usize) -> Self {
+            Self { max_flushes }
+        }
+    }
+
+    /// A plugin that also runs the [`StateFlush`] schedule in another schedule (e.g.
+    /// [`FixedStateFlush`]).
+    ///
+    /// If the other schedule is [`FixedStateFlush`], it will be added to the
+    /// [`FixedMainScheduleOrder`] after [`FixedPreUpdate`]. The `StateFlush` schedule will run
+    /// from a single exclusive system with no ordering constraints in the other schedule, so a
+    /// dedicated schedule like `FixedStateFlush` is recommended.
+    ///
+    /// Each state will flush in every flush schedule unless restricted with
+    /// [`ResolveStatePlugin::flush_in`](crate::schedule::resolve_state::ResolveStatePlugin::flush_in)
+    /// (e.g. `#[state(flush_in(FixedStateFlush))]`). This plugin can be added multiple times for
+    /// different schedules, and must be added after [`StatePlugin`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use pyri_state::prelude::*;
+    /// #
+    /// #[derive(State, Clone, PartialEq, Eq)]
+    /// #[state(flush_in(FixedStateFlush))]
+    /// enum Physics {
+    ///     Running,
+    ///     Paused,
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((StatePlugin, StateFlushInPlugin::new(FixedStateFlush)))
+    ///     .add_state::<Physics>();
+    /// ```
+    pub struct StateFlushInPlugin {
+        schedule: InternedScheduleLabel,
+    }
+
+    impl Plugin for StateFlushInPlugin {
+        fn build(&self, app: &mut App) {
+            let schedule = self.schedule;
+            if schedule == FixedStateFlush.intern() {
+                app.init_schedule(FixedStateFlush)
+                    .world_mut()
+                    .resource_mut::<FixedMainScheduleOrder>()
+                    .insert_after(FixedPreUpdate, FixedStateFlush);
+            }
+
+            app.add_systems(schedule, move |world: &mut World| {
+                run_state_flush(world, schedule)
+            });
+        }
+
+        fn is_unique(&self) -> bool {
+            false
+        }
+    }
+
+    impl StateFlushInPlugin {
+        /// Create a `StateFlushInPlugin` that runs the [`StateFlush`] schedule in `schedule`.
+        pub fn new(schedule: impl ScheduleLabel) -> Self {
+            Self {
+                schedule: schedule.intern(),
+            }
+        }
+    }
+
+    /// Run the [`StateFlush`] schedule from another schedule.
+    fn run_state_flush(world: &mut World, schedule: InternedScheduleLabel) {
+        world.resource_mut::<CurrentStateFlush>().schedule = schedule;
+        #[cfg(feature = "debug")]
+        crate::debug::flush_history::update_state_flush_history(world);
+        world.run_schedule(StateFlush);
+        settle_state_flush(world);
+        world.resource_mut::<CurrentStateFlush>().schedule = StateFlush.intern();
+    }
+
+    /// The schedule that re-runs the [`StateFlush`] schedule in settle mode.
+    #[derive(ScheduleLabel, Clone, Hash, PartialEq, Eq, Debug)]
+    struct SettleStateFlush;
+
+    /// A resource that sets the maximum number of flushes per frame in
+    /// [settle mode](StateSettlePlugin).
+    #[derive(Resource)]
+    struct StateSettle(usize);
+
+    /// A resource that lists the run conditions that check if any state is pending a flush.
+    ///
+    /// The run conditions are recorded even without [settle mode](StateSettlePlugin), so that
+    /// `StateSettlePlugin` and `State` types can be added in any order.
+    #[derive(Resource, Default)]
+    struct PendingStateFlush(Vec<BoxedCondition>);
+
+    /// Add a run condition that checks if the [`State`] type `S` is pending a flush in
+    /// [settle mode](StateSettlePlugin).
+    pub(crate) fn add_pending_flush_condition<S: State, M>(
+        app: &mut App,
+        condition: impl Condition<M>,
+    ) {
+        app.world_mut()
+            .resource_scope(|world, mut pending: Mut<PendingStateFlush>| {
+                let mut condition = IntoSystem::into_system(condition.and(can_flush::<S>));
+                condition.initialize(world);
+                pending.0.push(Box::new(condition));
+            });
+    }
+
+    fn is_flush_pending(world: &mut World) -> bool {
+        world
+            .try_resource_scope(|world, mut pending: Mut<PendingStateFlush>| {
+                pending.0.iter_mut().any(|condition| {
+                    condition.validate_param(world).is_ok() && condition.run((), world)
+                })
+            })
+            .unwrap_or(false)
+    }
+
+    /// Re-run the [`StateFlush`] schedule until no state is pending a flush, if settle mode is
+    /// enabled.
+    fn settle_state_flush(world: &mut World) {
+        let Some(&StateSettle(max_flushes)) = world.get_resource::<StateSettle>() else {
+            return;
+        };
+
+        // The `StateFlush` schedule has already run once this frame.
+        for _ in 1..max_flushes {
+            if !is_flush_pending(world) {
+                return;
+            }
+
+            #[cfg(feature = "debug")]
+            crate::debug::flush_history::update_state_flush_history(world);
+            world.run_schedule(StateFlush);
+        }
+
+        #[cfg(feature = "debug")]
+        if is_flush_pending(world) {
+            bevy_log::warn!("States did not settle after {max_flushes} flushes in one frame");
+        }
+    }
+
     /// An extension trait for [`App`] that provides methods for adding [`State`] types.
     pub trait AppExtState {
         /// Register a `State` type without initializing it.
@@ -60,7 +262,7 @@ mod app {
     impl AppExtState for App {
         fn register_state<S: RegisterState>(&mut self) -> &mut Self {
             if !state_exists::<S>(self.world()) {
-                S::register_state(self);
+                register_state::<S>(self);
             }
             self
         }
@@ -68,7 +270,7 @@ mod app {
         fn add_state<S: RegisterState>(&mut self) -> &mut Self {
             if !state_exists::<S>(self.world()) {
                 insert_state(self.world_mut(), None::<S::Next>);
-                S::register_state(self);
+                register_state::<S>(self);
             }
             self
         }
@@ -77,20 +279,38 @@ mod app {
             if !state_exists::<S>(self.world()) {
                 let next = S::Next::from_world(self.world_mut());
                 insert_state(self.world_mut(), Some(next));
-                S::register_state(self);
+                register_state::<S>(self);
             }
             self
         }
 
         fn insert_state<T: NextState<State: RegisterState>>(&mut self, next: T) -> &mut Self {
+            // Check before inserting, since inserting makes the state exist.
+            let exists = state_exists::<T::State>(self.world());
             insert_state(self.world_mut(), Some(next));
-            if !state_exists::<T::State>(self.world()) {
-                T::State::register_state(self);
+            if !exists {
+                register_state::<T::State>(self);
             }
             self
         }
     }
 
+    fn register_state<S: RegisterState>(app: &mut App) {
+        S::register_state(app);
+        #[cfg(feature = "debug")]
+        {
+            use crate::debug::flush_history::{StateFlushHistoryPlugin, schedule_record_flush};
+
+            crate::debug::registry::StateRegistry::register_default::<S>(app.world_mut());
+            // States added before the plugin are handled by `StateFlushHistoryPlugin`.
+            if app.is_plugin_added::<StateFlushHistoryPlugin>() {
+                schedule_record_flush::<S>(app.get_schedule_mut(StateFlush).unwrap());
+            }
+        }
+        #[cfg(feature = "snapshot")]
+        crate::extra::snapshot::register_snapshot::<S>(app.world_mut());
+    }
+
     /// A [`State`] type that can be registered with an [`App`].
     pub trait RegisterState: State {
         /// Register this state type with the app.
@@ -99,9 +319,11 @@ mod app {
 }
 
 use crate::{
-    next_state::{NextState, TriggerStateFlush},
+    access::NextMut,
+    next_state::{NextState, NextStateMut, TriggerStateFlush, warn_missing},
     prelude::State,
-    state::LocalState,
+    schedule::{CurrentStateFlush, StateFlush},
+    state::{LocalState, StateMut},
 };
 
 fn state_exists<S: State>(world: &World) -> bool {
@@ -113,7 +335,69 @@ fn insert_state<Next: NextState>(world: &mut World, next: Option<Next>) {
     world.init_resource::<TriggerStateFlush<Next::State>>();
 }
 
-/// An extension trait for [`Commands`] that provides methods for adding [`State`] types.
+/// An extension trait for [`World`] that provides methods for flushing [`State`] types
+/// immediately.
+///
+/// This is useful to apply states during startup or in tests without waiting for the next
+/// update.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use pyri_state::prelude::*;
+/// #
+/// #[derive(State, Clone, PartialEq, Eq)]
+/// enum Screen {
+///     Title,
+///     Gameplay,
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins(StatePlugin).add_state::<Screen>();
+///
+/// let world = app.world_mut();
+/// world.resource_mut::<NextStateBuffer<Screen>>().enter(Screen::Gameplay);
+/// world.flush_state::<Screen>();
+/// assert!(world.resource::<Screen>() == &Screen::Gameplay);
+/// ```
+pub trait WorldExtState {
+    /// Run the [`StateFlush`] schedule immediately to flush every triggered `State` type.
+    ///
+    /// `State` types that can't flush in the [`CurrentStateFlush`] schedule will not flush.
+    /// Does nothing if the `StateFlush` schedule is already running.
+    fn flush_states(&mut self);
+
+    /// Run the [`StateFlush`] schedule immediately to flush the `State` type `S` if triggered.
+    ///
+    /// Other `State` types will not flush, even if they depend on `S`. Does nothing if the
+    /// `StateFlush` schedule is already running.
+    fn flush_state<S: State>(&mut self);
+}
+
+impl WorldExtState for World {
+    fn flush_states(&mut self) {
+        #[cfg(feature = "debug")]
+        crate::debug::flush_history::update_state_flush_history(self);
+        let _ = self.try_run_schedule(StateFlush);
+    }
+
+    fn flush_state<S: State>(&mut self) {
+        let old = self
+            .get_resource_or_init::<CurrentStateFlush>()
+            .state
+            .replace(TypeId::of::<S>());
+        self.flush_states();
+        self.resource_mut::<CurrentStateFlush>().state = old;
+    }
+}
+
+/// An extension trait for [`Commands`] that provides methods for adding, setting, and flushing
+/// [`State`] types.
+///
+/// This is useful to request transitions from observers or gameplay code without a
+/// [`NextMut`] system param. Commands on a state type that hasn't been added do nothing (with a
+/// warning if the `debug` feature is enabled).
 pub trait CommandsExtState {
     /// Queue a command to initialize a `State` type with an empty `NextState`.
     ///
@@ -125,6 +409,26 @@ pub trait CommandsExtState {
 
     /// Queue a command to initialize a `State` type with a specific `NextState`.
     fn insert_state<T: NextState>(&mut self, next: T);
+
+    /// Queue a command to set the next state of a `State` type to a specific value and trigger
+    /// a flush.
+    fn set_state<S: StateMut>(&mut self, state: S);
+
+    /// Queue a command to disable the next state of a `State` type and trigger a flush.
+    fn disable_state<S: StateMut>(&mut self);
+
+    /// Queue a command to trigger a `State` type to flush in the [`StateFlush`] schedule.
+    fn trigger_state<S: State>(&mut self);
+
+    /// Queue a command to flush every triggered `State` type immediately.
+    ///
+    /// See [`WorldExtState::flush_states`].
+    fn flush_states(&mut self);
+
+    /// Queue a command to flush a `State` type immediately if triggered.
+    ///
+    /// See [`WorldExtState::flush_state`].
+    fn flush_state<S: State>(&mut self);
 }
 
 impl CommandsExtState for Commands<'_, '_> {
@@ -148,6 +452,43 @@ impl CommandsExtState for Commands<'_, '_> {
     fn insert_state<T: NextState>(&mut self, next: T) {
         self.queue(|world: &mut World| insert_state(world, Some(next)));
     }
+
+    fn set_state<S: StateMut>(&mut self, state: S) {
+        self.queue(|world: &mut World| set_next_state(world, Some(state)));
+    }
+
+    fn disable_state<S: StateMut>(&mut self) {
+        self.queue(|world: &mut World| set_next_state::<S>(world, None));
+    }
+
+    fn trigger_state<S: State>(&mut self) {
+        self.queue(|world: &mut World| {
+            if let Some(mut trigger) = world.get_resource_mut::<TriggerStateFlush<S>>() {
+                trigger.0 = true;
+            } else {
+                warn_missing::<TriggerStateFlush<S>>(None);
+            }
+        });
+    }
+
+    fn flush_states(&mut self) {
+        self.queue(|world: &mut World| world.flush_states());
+    }
+
+    fn flush_state<S: State>(&mut self) {
+        self.queue(|world: &mut World| world.flush_state::<S>());
+    }
+}
+
+fn set_next_state<S: StateMut>(world: &mut World, state: Option<S>) {
+    if !world.contains_resource::<S::Next>() {
+        warn_missing::<S::Next>(None);
+        return;
+    }
+
+    let mut system_state = SystemState::<NextMut<S>>::new(world);
+    system_state.get_mut(world).trigger().set(state);
+    system_state.apply(world);
 }
 
 fn local_state_exists<S: LocalState>(entity: &EntityWorldMut) -> bool {
@@ -164,8 +505,11 @@ fn insert_local_state<Next: NextState<State: LocalState<Next = Next>>>(
     ));
 }
 
-/// An extension trait for [`EntityCommands`] that provides methods for adding
-/// [`LocalState`] types.
+/// An extension trait for [`EntityCommands`] that provides methods for adding, setting, and
+/// triggering [`LocalState`] types.
+///
+/// Commands on an entity without the local state do nothing (with a warning if the `debug`
+/// feature is enabled).
 pub trait EntityCommandsExtState {
     /// Queue a command to initialize a `LocalState` type with an empty `NextState`.
     ///
@@ -177,6 +521,21 @@ pub trait EntityCommandsExtState {
 
     /// Queue a command to initialize a `LocalState` type with a specific `NextState`.
     fn insert_state<T: NextState<State: LocalState<Next = T>>>(&mut self, next: T);
+
+    /// Queue a command to set the next state of a `LocalState` type to a specific value and
+    /// trigger a flush.
+    fn set_state<S: LocalState<Next: NextStateMut + Component<Mutability = Mutable>>>(
+        &mut self,
+        state: S,
+    );
+
+    /// Queue a command to disable the next state of a `LocalState` type and trigger a flush.
+    fn disable_state<S: LocalState<Next: NextStateMut + Component<Mutability = Mutable>>>(
+        &mut self,
+    );
+
+    /// Queue a command to trigger a `LocalState` type to flush in the [`StateFlush`] schedule.
+    fn trigger_state<S: LocalState>(&mut self);
 }
 
 impl EntityCommandsExtState for EntityCommands<'_> {
@@ -200,4 +559,64 @@ impl EntityCommandsExtState for EntityCommands<'_> {
     fn insert_state<T: NextState<State: LocalState<Next = T>>>(&mut self, next: T) {
         self.queue(|mut entity: EntityWorldMut| insert_local_state(&mut entity, Some(next)));
     }
+
+    fn set_state<S: LocalState<Next: NextStateMut + Component<Mutability = Mutable>>>(
+        &mut self,
+        state: S,
+    ) {
+        self.queue(|mut entity: EntityWorldMut| {
+            set_local_next_state(&mut entity, Some(state));
+            trigger_local_state::<S>(&mut entity);
+        });
+    }
+
+    fn disable_state<S: LocalState<Next: NextStateMut + Component<Mutability = Mutable>>>(
+        &mut self,
+    ) {
+        self.queue(|mut entity: EntityWorldMut| {
+            set_local_next_state::<S>(&mut entity, None);
+            trigger_local_state::<S>(&mut entity);
+        });
+    }
+
+    fn trigger_state<S: LocalState>(&mut self) {
+        self.queue(|mut entity: EntityWorldMut| {
+            if let Some(mut trigger) = entity.get_mut::<TriggerStateFlush<S>>() {
+                trigger.0 = true;
+            } else {
+                warn_missing::<TriggerStateFlush<S>>(Some(entity.id()));
+            }
+        });
+    }
+}
+
+// A missing state is already reported by `set_local_next_state`.
+fn trigger_local_state<S: LocalState>(entity: &mut EntityWorldMut) {
+    if let Some(mut trigger) = entity.get_mut::<TriggerStateFlush<S>>() {
+        trigger.0 = true;
+    }
+}
+
+pub(crate) fn set_local_next_state<
+    S: LocalState<Next: NextStateMut + Component<Mutability = Mutable>>,
+>(
+    entity: &mut EntityWorldMut,
+    state: Option<S>,
+) {
+    let id = entity.id();
+    entity.world_scope(|world| {
+        let mut system_state = SystemState::<(
+            Query<&mut S::Next>,
+            StaticSystemParam<<S::Next as NextStateMut>::ParamMut>,
+        )>::new(world);
+        {
+            let (mut next_query, mut next_param) = system_state.get_mut(world);
+            if let Ok(mut next) = next_query.get_mut(id) {
+                next.set_next_state(&mut next_param, state);
+            } else {
+                warn_missing::<S::Next>(Some(id));
+            }
+        }
+        system_state.apply(world);
+    });
 }
@@ -45,6 +45,50 @@ pub trait State: Resource + Sized {
     /// The [`NextState`] type that determines the next state for this state type.
     type Next: NextState<State = Self>;
 
+    /// Whether this state type is hierarchical (e.g. an enum with nested state values).
+    ///
+    /// In a hierarchical state type, the exit and enter hooks of a
+    /// [`StatePattern`](crate::pattern::StatePattern) are skipped if the pattern matches both
+    /// the current and next states. This way, only the levels below the least common ancestor
+    /// of the two states will exit and enter:
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use pyri_state::prelude::*;
+    /// #
+    /// #[derive(State, Clone, PartialEq, Eq)]
+    /// #[state(hierarchical)]
+    /// enum Screen {
+    ///     Title,
+    ///     Gameplay(Phase),
+    /// }
+    ///
+    /// #[derive(Clone, PartialEq, Eq)]
+    /// enum Phase {
+    ///     Explore,
+    ///     Combat,
+    /// }
+    ///
+    /// # fn spawn_level() {}
+    /// # fn start_music() {}
+    /// #
+    /// # fn plugin(app: &mut App) {
+    /// app.add_systems(StateFlush, (
+    ///     // Won't run on `Screen::Gameplay(Phase::Explore)` -> `Screen::Gameplay(Phase::Combat)`.
+    ///     state!(Screen::Gameplay(_)).on_enter(spawn_level),
+    ///     // Will run on `Screen::Gameplay(Phase::Explore)` -> `Screen::Gameplay(Phase::Combat)`.
+    ///     state!(Screen::Gameplay(Phase::Combat)).on_enter(start_music),
+    /// ));
+    /// # }
+    /// ```
+    ///
+    /// [`Self::ANY`] hooks are not skipped, so they still run on every exit and enter.
+    ///
+    /// Use [`on_refresh`](crate::pattern::StatePatternExtEq::on_refresh) or a
+    /// [`StateTransPattern`](crate::pattern::StateTransPattern) to react to flushes that don't
+    /// change the state.
+    const HIERARCHICAL: bool = false;
+
     /// The [`AnyStatePattern`] for this state type.
     const ANY: AnyStatePattern<Self> = AnyStatePattern(PhantomData);
 
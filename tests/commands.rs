@@ -0,0 +1,126 @@
+//! Tests for state commands.
+
+#![cfg(feature = "stack")]
+
+use bevy::prelude::*;
+use pyri_state::{next_state::stack::NextStateStackCommandsExt as _, prelude::*};
+
+#[derive(State, Component, Clone, PartialEq, Eq, Debug)]
+#[state(local)]
+enum Screen {
+    Title,
+    Gameplay,
+}
+
+#[derive(State, Component, Clone, PartialEq, Eq, Debug)]
+#[state(local, next(NextStateStack<Self>))]
+enum Menu {
+    Main,
+    Settings,
+}
+
+fn run(app: &mut App, f: impl FnOnce(&mut Commands)) {
+    f(&mut app.world_mut().commands());
+    app.world_mut().flush();
+    app.update();
+}
+
+#[test]
+fn set_and_trigger_state() {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin).add_state::<Screen>();
+
+    run(&mut app, |commands| commands.set_state(Screen::Title));
+    assert_eq!(app.world().get_resource::<Screen>(), Some(&Screen::Title));
+
+    let entity = app.world_mut().spawn_empty().id();
+    run(&mut app, |commands| {
+        let mut entity = commands.entity(entity);
+        entity.add_state::<Screen>();
+        entity.set_state(Screen::Gameplay);
+    });
+    assert_eq!(app.world().get::<Screen>(entity), Some(&Screen::Gameplay));
+
+    run(&mut app, |commands| {
+        commands.disable_state::<Screen>();
+        commands.entity(entity).disable_state::<Screen>();
+    });
+    assert_eq!(app.world().get_resource::<Screen>(), None);
+    assert_eq!(app.world().get::<Screen>(entity), None);
+}
+
+#[test]
+fn stack_commands() {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .insert_state(NextStateStack::with_base(Menu::Main));
+
+    run(&mut app, |commands| {
+        commands.state_stack_push(Menu::Settings);
+        commands.trigger_state::<Menu>();
+    });
+    assert_eq!(app.world().get_resource::<Menu>(), Some(&Menu::Settings));
+
+    let entity = app.world_mut().spawn_empty().id();
+    run(&mut app, |commands| {
+        let mut entity = commands.entity(entity);
+        entity.insert_state(NextStateStack::with_base(Menu::Main));
+        entity
+            .state_stack_push(Menu::Settings)
+            .state_stack_pop::<Menu>();
+        entity.trigger_state::<Menu>();
+    });
+    assert_eq!(app.world().get::<Menu>(entity), Some(&Menu::Main));
+}
+
+#[test]
+fn missing_state_does_nothing() {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin);
+    let entity = app.world_mut().spawn_empty().id();
+
+    run(&mut app, |commands| {
+        commands.set_state(Screen::Title);
+        commands.trigger_state::<Screen>();
+        commands.state_stack_push(Menu::Main);
+        let mut entity = commands.entity(entity);
+        entity.set_state(Screen::Title);
+        entity.trigger_state::<Screen>();
+        entity.state_stack_push(Menu::Main);
+    });
+    assert_eq!(app.world().get_resource::<Screen>(), None);
+    assert_eq!(app.world().get::<Screen>(entity), None);
+}
+
+#[derive(State, Component, Clone, PartialEq, Eq, Debug)]
+#[state(no_defaults, local, apply_flush)]
+enum Level {
+    First,
+}
+
+#[test]
+fn set_state_triggers_without_detect_change() {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin).add_state::<Level>();
+    let entity = app.world_mut().spawn_empty().id();
+
+    run(&mut app, |commands| {
+        commands.set_state(Level::First);
+        commands
+            .entity(entity)
+            .insert_state(NextStateBuffer::<Level>::disabled());
+    });
+    assert_eq!(app.world().get_resource::<Level>(), Some(&Level::First));
+
+    run(&mut app, |commands| {
+        commands.entity(entity).set_state(Level::First)
+    });
+    assert_eq!(app.world().get::<Level>(entity), Some(&Level::First));
+
+    run(&mut app, |commands| {
+        commands.disable_state::<Level>();
+        commands.entity(entity).disable_state::<Level>();
+    });
+    assert_eq!(app.world().get_resource::<Level>(), None);
+    assert_eq!(app.world().get::<Level>(entity), None);
+}
@@ -0,0 +1,46 @@
+//! Helpers shared by the integration tests.
+
+// Each test crate only uses some of the helpers.
+#![allow(dead_code)]
+
+use bevy::prelude::*;
+use pyri_state::prelude::*;
+
+/// A resource that records which hook systems ran.
+#[derive(Resource)]
+pub struct Log<T: 'static + Send + Sync = &'static str>(pub Vec<T>);
+
+impl<T: 'static + Send + Sync> Default for Log<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+/// Set the next value of the [`State`] type `S`.
+pub fn enter<S: State<Next = NextStateBuffer<S>>>(app: &mut App, state: S) {
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<S>>()
+        .enter(state);
+}
+
+/// Build a hook system that records a message in the [`Log`].
+pub fn log(message: &'static str) -> impl Fn(ResMut<Log>) {
+    move |mut log| log.0.push(message)
+}
+
+/// Run an update and take the messages in the [`Log`].
+///
+/// See [`update_log`].
+pub fn update(app: &mut App) -> Vec<&'static str> {
+    update_log(app)
+}
+
+/// Run an update and take the entries of the [`Log`].
+///
+/// The entries are sorted, because hook systems in the same system set can run in any order.
+pub fn update_log<T: 'static + Send + Sync + Ord>(app: &mut App) -> Vec<T> {
+    app.update();
+    let mut log = core::mem::take(&mut app.world_mut().resource_mut::<Log<T>>().0);
+    log.sort();
+    log
+}
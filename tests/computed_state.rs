@@ -0,0 +1,105 @@
+//! Tests for computed states.
+
+use bevy::prelude::*;
+use pyri_state::prelude::*;
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+enum Menu {
+    Main,
+    Pause,
+}
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+enum Dialog {
+    Open,
+    Closed,
+}
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+#[state(computed)]
+struct IsPaused;
+
+impl ComputedState for IsPaused {
+    type Sources = (Menu, Dialog);
+
+    fn compute((menu, dialog): (Option<&Menu>, Option<&Dialog>)) -> Option<Self> {
+        matches!(
+            (menu, dialog),
+            (Some(Menu::Pause), _) | (_, Some(Dialog::Open))
+        )
+        .then_some(IsPaused)
+    }
+}
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+#[state(computed)]
+struct InMenu(Menu);
+
+impl ComputedState for InMenu {
+    type Sources = Menu;
+
+    fn compute(menu: Option<&Menu>) -> Option<Self> {
+        menu.cloned().map(InMenu)
+    }
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .add_state::<Menu>()
+        .add_state::<Dialog>()
+        .add_state::<IsPaused>()
+        .add_state::<InMenu>();
+    app
+}
+
+fn set<S: State<Next = NextStateBuffer<S>>>(app: &mut App, state: Option<S>) {
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<S>>()
+        .set(state);
+}
+
+fn is_paused(app: &App) -> bool {
+    app.world().contains_resource::<IsPaused>()
+}
+
+#[test]
+fn recomputed_from_multiple_sources() {
+    let mut app = app();
+    set(&mut app, Some(Menu::Main));
+    set(&mut app, Some(Dialog::Closed));
+    app.update();
+    assert!(!is_paused(&app));
+
+    set(&mut app, Some(Menu::Pause));
+    app.update();
+    assert!(is_paused(&app));
+
+    // Both sources change in the same frame.
+    set(&mut app, Some(Menu::Main));
+    set(&mut app, Some(Dialog::Open));
+    app.update();
+    assert!(is_paused(&app));
+
+    set(&mut app, None::<Dialog>);
+    app.update();
+    assert!(!is_paused(&app));
+}
+
+#[test]
+fn recomputed_from_single_source() {
+    let mut app = app();
+    app.update();
+    assert_eq!(app.world().get_resource::<InMenu>(), None);
+
+    set(&mut app, Some(Menu::Pause));
+    app.update();
+    assert_eq!(
+        app.world().get_resource::<InMenu>(),
+        Some(&InMenu(Menu::Pause)),
+    );
+
+    set(&mut app, None::<Menu>);
+    app.update();
+    assert_eq!(app.world().get_resource::<InMenu>(), None);
+}
@@ -0,0 +1,82 @@
+//! Tests for time-delayed state flushes.
+
+#![cfg(feature = "delay")]
+
+use core::time::Duration;
+
+use bevy::{
+    app::Plugins,
+    prelude::*,
+    time::{TimePlugin, TimeUpdateStrategy},
+};
+use pyri_state::{next_state::TriggerStateFlush, prelude::*};
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+#[state(delay)]
+enum Phase {
+    Victory,
+    Results,
+}
+
+fn app<M>(plugins: impl Plugins<M>) -> App {
+    let mut app = App::new();
+    app.add_plugins((TimePlugin, plugins))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .add_state::<Phase>();
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<Phase>>()
+        .enter(Phase::Victory);
+    app.update();
+    app
+}
+
+// Count the frames until the delayed flush is applied.
+fn frames_until_results(app: &mut App) -> usize {
+    app.world_mut()
+        .resource_mut::<DelayedStateFlush<Phase>>()
+        .enter_after(Phase::Results, Duration::from_secs(1));
+    for frame in 1..=20 {
+        app.update();
+        if app.world().resource::<Phase>() == &Phase::Results {
+            return frame;
+        }
+    }
+    panic!("the delayed flush was never applied");
+}
+
+#[test]
+fn delayed_flush() {
+    let mut app = app(StatePlugin);
+    assert!(app.world().resource::<Phase>() == &Phase::Victory);
+    assert_eq!(frames_until_results(&mut app), 10);
+}
+
+#[test]
+fn delayed_flush_with_multiple_flushes_per_frame() {
+    let mut app = app((
+        StatePlugin,
+        StateSettlePlugin::new(4),
+        StateFlushInPlugin::new(FixedStateFlush),
+    ));
+    assert_eq!(frames_until_results(&mut app), 10);
+}
+
+#[test]
+fn canceled_by_flush() {
+    let mut app = app(StatePlugin);
+    app.world_mut()
+        .resource_mut::<DelayedStateFlush<Phase>>()
+        .enter_after(Phase::Results, Duration::from_secs(1));
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<Phase>>()
+        .enter(Phase::Victory);
+    app.world_mut().resource_mut::<TriggerStateFlush<Phase>>().0 = true;
+    app.update();
+    assert!(
+        !app.world()
+            .resource::<DelayedStateFlush<Phase>>()
+            .is_pending()
+    );
+}
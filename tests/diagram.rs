@@ -0,0 +1,58 @@
+//! Tests for state diagrams.
+
+#![cfg(all(feature = "debug", feature = "bevy_app"))]
+
+use bevy::prelude::*;
+use pyri_state::{debug::diagram::StateDiagram, prelude::*};
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+#[state(transitions(Title => Gameplay, Gameplay => Title | Credits))]
+enum Screen {
+    Title,
+    Gameplay,
+    Credits,
+}
+
+fn spawn_level() {}
+
+fn despawn_level() {}
+
+fn play_intro() {}
+
+fn save_score() {}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .add_state::<Screen>()
+        .add_systems(
+            StateFlush,
+            (
+                Screen::Gameplay.on_edge(despawn_level, spawn_level),
+                (Screen::Title, Screen::Gameplay).on_trans(play_intro),
+                (Screen::Gameplay, Screen::Credits).on_exit(save_score),
+            ),
+        );
+    app
+}
+
+#[test]
+fn edges_come_from_transition_table() {
+    let dot = StateDiagram::from_app::<Screen>(&app())
+        .with_transitions(Screen::transitions())
+        .to_dot();
+
+    assert!(dot.contains("\"Title\" -> \"Gameplay\";\n"));
+    assert!(dot.contains("\"Gameplay\" -> \"Title\";\n"));
+    assert!(dot.contains("\"Gameplay\" -> \"Credits\";\n"));
+    assert_eq!(dot.matches("->").count(), 3);
+}
+
+#[test]
+fn hooks_are_listed_by_system_set() {
+    let mermaid = StateDiagram::from_app::<Screen>(&app()).to_mermaid();
+
+    assert!(mermaid.contains(
+        "hooks>\"Exit: diagram::despawn_level<br>Exit: diagram::save_score<br>Trans: diagram::play_intro<br>Enter: diagram::spawn_level\"]"
+    ));
+}
@@ -0,0 +1,145 @@
+//! Tests for the state flush history.
+
+#![cfg(all(feature = "debug", feature = "bevy_reflect"))]
+
+use bevy::prelude::*;
+use pyri_state::{
+    debug::{
+        StateDebugSettings,
+        flush_history::{StateFlushHistory, StateFlushHistoryPlugin},
+    },
+    next_state::TriggerStateFlush,
+    prelude::*,
+};
+
+#[derive(State, Reflect, Component, Clone, PartialEq, Eq)]
+#[state(local)]
+#[reflect(Resource, Component)]
+enum Screen {
+    Title,
+    Gameplay,
+}
+
+#[derive(State, Clone, PartialEq, Eq)]
+struct Paused;
+
+#[derive(State, Clone, PartialEq, Eq)]
+#[state(flush_in(PostUpdate))]
+struct Tooltip;
+
+fn spawn_level() {}
+
+fn app(record_local: bool) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatePlugin))
+        .insert_resource(StateDebugSettings {
+            record_flush: true,
+            record_local,
+            ..default()
+        })
+        // States can be added before or after the plugin.
+        .add_state::<Screen>()
+        .add_plugins(StateFlushHistoryPlugin)
+        .add_state::<Paused>()
+        .register_type::<Screen>()
+        .add_systems(StateFlush, Screen::Gameplay.on_enter(spawn_level));
+    app
+}
+
+fn history<S: State>(app: &App) -> Vec<String> {
+    app.world()
+        .resource::<StateFlushHistory>()
+        .iter_state::<S>()
+        .map(|record| {
+            let old = record.old.as_deref().unwrap_or("disabled");
+            let new = record.new.as_deref().unwrap_or("disabled");
+            format!("{old} -> {new} {:?}", record.enter)
+        })
+        .collect()
+}
+
+#[test]
+fn records_without_log_flush() {
+    let mut app = app(false);
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<Screen>>()
+        .enter(Screen::Title);
+    app.update();
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<Screen>>()
+        .enter(Screen::Gameplay);
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<Paused>>()
+        .enter(Paused);
+    app.update();
+
+    assert_eq!(
+        history::<Screen>(&app),
+        [
+            "disabled -> Title []",
+            "Title -> Gameplay [\"flush_history::spawn_level\"]",
+        ],
+    );
+    // Values of state types that aren't reflectable aren't recorded.
+    assert_eq!(history::<Paused>(&app), ["disabled -> enabled []"]);
+}
+
+#[test]
+fn records_local_with_record_local() {
+    for record_local in [false, true] {
+        let mut app = app(record_local);
+        let entity = app.world_mut().spawn_empty().id();
+        app.world_mut()
+            .commands()
+            .entity(entity)
+            .insert_state(NextStateBuffer::enabled(Screen::Title));
+        app.world_mut().flush();
+        app.world_mut()
+            .get_mut::<TriggerStateFlush<Screen>>(entity)
+            .unwrap()
+            .0 = true;
+        app.update();
+
+        let history = app.world().resource::<StateFlushHistory>();
+        let records = history
+            .iter_state::<Screen>()
+            .map(|x| (x.entity, x.old.as_deref(), x.new.as_deref()))
+            .collect::<Vec<_>>();
+        if record_local {
+            assert_eq!(records, [(Some(entity), None, Some("Title"))]);
+        } else {
+            assert!(records.is_empty());
+        }
+    }
+}
+
+#[test]
+fn records_once_per_flush_in_other_schedule() {
+    let mut app = app(false);
+    app.add_plugins(StateFlushInPlugin::new(PostUpdate))
+        .add_state::<Tooltip>();
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<Tooltip>>()
+        .enter(Tooltip);
+    app.world_mut()
+        .resource_mut::<TriggerStateFlush<Tooltip>>()
+        .0 = true;
+    app.update();
+    app.update();
+
+    assert_eq!(history::<Tooltip>(&app), ["disabled -> enabled []"]);
+}
+
+#[test]
+fn not_recorded_without_plugin() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatePlugin))
+        .insert_resource(StateDebugSettings {
+            record_flush: true,
+            ..default()
+        })
+        .add_state::<Paused>();
+    app.update();
+
+    assert!(!app.world().contains_resource::<StateFlushHistory>());
+}
@@ -0,0 +1,86 @@
+//! Tests for flushing states in other schedules.
+
+mod common;
+
+use core::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use pyri_state::prelude::*;
+
+use common::enter;
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+enum Screen {
+    Title,
+}
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+#[state(flush_in(PostUpdate))]
+enum Tooltip {
+    Shown,
+}
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+#[state(flush_in(FixedStateFlush))]
+enum Physics {
+    Running,
+}
+
+/// Whether each state was enabled in `Update`.
+#[derive(Resource, Default, Debug, PartialEq)]
+struct EnabledInUpdate(bool, bool);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatePlugin,
+        StateFlushInPlugin::new(PostUpdate),
+        StateFlushInPlugin::new(FixedStateFlush),
+    ))
+    .init_resource::<EnabledInUpdate>()
+    .add_state::<Screen>()
+    .add_state::<Tooltip>()
+    .add_state::<Physics>()
+    .add_systems(
+        Update,
+        |screen: CurrentRef<Screen>,
+         tooltip: CurrentRef<Tooltip>,
+         mut x: ResMut<EnabledInUpdate>| {
+            *x = EnabledInUpdate(screen.is_enabled(), tooltip.is_enabled());
+        },
+    );
+    app
+}
+
+#[test]
+fn flush_in_other_schedule() {
+    let mut app = app();
+    enter(&mut app, Screen::Title);
+    enter(&mut app, Tooltip::Shown);
+    app.update();
+
+    // `Screen` flushes in `StateFlush` before `Update`, but `Tooltip` waits for `PostUpdate`.
+    assert_eq!(
+        *app.world().resource::<EnabledInUpdate>(),
+        EnabledInUpdate(true, false),
+    );
+    assert!(app.world().contains_resource::<Tooltip>());
+}
+
+#[test]
+fn flush_in_fixed_schedule() {
+    let mut app = app();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(1)));
+    app.update();
+    enter(&mut app, Physics::Running);
+    app.update();
+    assert!(!app.world().contains_resource::<Physics>());
+
+    // Run the fixed timestep loop.
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        100,
+    )));
+    app.update();
+    assert!(app.world().contains_resource::<Physics>());
+}
@@ -0,0 +1,89 @@
+//! Tests for flush loop detection.
+
+#![cfg(feature = "debug")]
+
+use bevy::{diagnostic::FrameCount, prelude::*};
+use pyri_state::{
+    debug::{StateDebugSettings, flush_loop::StateFlushLoopDetected},
+    prelude::*,
+};
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+#[state(detect_loop)]
+enum Screen {
+    Title,
+    Gameplay,
+}
+
+fn app(drive: fn(NextMut<Screen>, u32)) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatePlugin))
+        .insert_resource(StateDebugSettings {
+            loop_frames: 6,
+            ..default()
+        })
+        .add_state::<Screen>()
+        .add_systems(
+            Update,
+            move |next: NextMut<Screen>, frame: Res<FrameCount>| drive(next, frame.0),
+        );
+    app
+}
+
+fn detected(app: &mut App) -> Vec<Vec<Option<Screen>>> {
+    app.world_mut()
+        .resource_mut::<Events<StateFlushLoopDetected<Screen>>>()
+        .drain()
+        .map(|event| event.cycle)
+        .collect()
+}
+
+#[test]
+fn ping_pong_is_detected() {
+    let mut app = app(|mut next, frame| {
+        let screen = if frame % 2 == 0 {
+            Screen::Title
+        } else {
+            Screen::Gameplay
+        };
+        next.trigger().enter(screen);
+    });
+    for _ in 0..7 {
+        app.update();
+    }
+
+    let detected = detected(&mut app);
+    assert_eq!(detected.len(), 1);
+    assert_eq!(detected[0].len(), 2);
+}
+
+#[test]
+fn refresh_is_not_detected() {
+    let mut app = app(|mut next, _| {
+        next.trigger().enter(Screen::Gameplay);
+    });
+    for _ in 0..20 {
+        app.update();
+    }
+
+    assert!(detected(&mut app).is_empty());
+}
+
+#[test]
+fn skipped_without_frame_count() {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .insert_resource(StateDebugSettings {
+            loop_frames: 2,
+            ..default()
+        })
+        .add_state::<Screen>();
+    for screen in [Screen::Title, Screen::Gameplay, Screen::Title] {
+        app.world_mut()
+            .resource_mut::<NextStateBuffer<Screen>>()
+            .enter(screen);
+        app.update();
+    }
+
+    assert!(detected(&mut app).is_empty());
+}
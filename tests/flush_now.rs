@@ -0,0 +1,84 @@
+//! Tests for flushing states immediately.
+
+use bevy::prelude::*;
+use pyri_state::prelude::*;
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+enum Screen {
+    Title,
+    Gameplay,
+}
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+enum Music {
+    Calm,
+}
+
+#[derive(Resource, Default)]
+struct LevelSpawned(bool);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .init_resource::<LevelSpawned>()
+        .add_state::<Screen>()
+        .add_state::<Music>()
+        .add_systems(
+            StateFlush,
+            Screen::Gameplay.on_enter(|mut spawned: ResMut<LevelSpawned>| spawned.0 = true),
+        );
+    app
+}
+
+fn enter<S: State<Next = NextStateBuffer<S>>>(world: &mut World, state: S) {
+    world.resource_mut::<NextStateBuffer<S>>().enter(state);
+}
+
+#[test]
+fn flush_states_without_update() {
+    let mut app = app();
+    let world = app.world_mut();
+    enter(world, Screen::Gameplay);
+    enter(world, Music::Calm);
+    world.flush_states();
+
+    assert_eq!(world.get_resource::<Screen>(), Some(&Screen::Gameplay));
+    assert_eq!(world.get_resource::<Music>(), Some(&Music::Calm));
+    assert!(world.resource::<LevelSpawned>().0);
+}
+
+#[test]
+fn flush_state_only_flushes_one_type() {
+    let mut app = app();
+    let world = app.world_mut();
+    enter(world, Screen::Title);
+    enter(world, Music::Calm);
+    world.flush_state::<Screen>();
+
+    assert_eq!(world.get_resource::<Screen>(), Some(&Screen::Title));
+    assert_eq!(world.get_resource::<Music>(), None);
+
+    // The other state type is still triggered.
+    app.update();
+    assert_eq!(app.world().get_resource::<Music>(), Some(&Music::Calm));
+}
+
+#[test]
+fn flush_states_command_in_startup() {
+    let mut app = app();
+    app.add_systems(
+        Startup,
+        (
+            |mut screen: NextMut<Screen>, mut commands: Commands| {
+                screen.trigger().enter(Screen::Gameplay);
+                commands.flush_states();
+            },
+            |screen: CurrentRef<Screen>, spawned: Res<LevelSpawned>| {
+                assert_eq!(screen.get(), Some(&Screen::Gameplay));
+                assert!(spawned.0);
+            },
+        )
+            .chain(),
+    );
+    app.update();
+}
@@ -0,0 +1,116 @@
+//! Tests for state flush observers.
+
+use bevy::{ecs::schedule::ScheduleBuildSettings, prelude::*};
+use pyri_state::{
+    next_state::TriggerStateFlush,
+    prelude::*,
+    schedule::{ResolveStateSet, flush_observer::OnStateEnter},
+};
+
+#[derive(State, Component, Clone, PartialEq, Eq, Debug)]
+#[state(local, flush_observer)]
+enum Screen {
+    Title,
+    Gameplay,
+}
+
+#[derive(Resource, Default)]
+struct Log(Vec<String>);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .add_state::<Screen>()
+        .init_resource::<Log>()
+        .add_observer(
+            |trigger: Trigger<OnStateExit<Screen>>, mut log: ResMut<Log>| {
+                log.0.push(format!("exit {:?}", trigger.0));
+            },
+        )
+        .add_observer(
+            |trigger: Trigger<OnStateTrans<Screen>>, mut log: ResMut<Log>| {
+                log.0
+                    .push(format!("trans {:?} {:?}", trigger.old, trigger.new));
+            },
+        )
+        .add_observer(
+            |trigger: Trigger<OnStateEnter<Screen>>, mut log: ResMut<Log>| {
+                log.0.push(format!("enter {:?}", trigger.0));
+            },
+        );
+    app
+}
+
+#[test]
+fn global_observers() {
+    let mut app = app();
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<Screen>>()
+        .enter(Screen::Title);
+    app.update();
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<Screen>>()
+        .enter(Screen::Gameplay);
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<Log>().0,
+        [
+            "enter Title",
+            "exit Title",
+            "trans Title Gameplay",
+            "enter Gameplay",
+        ],
+    );
+}
+
+#[test]
+fn observers_run_within_the_set() {
+    let mut app = app();
+    // Without sync points, deferred triggers would only run at the end of the schedule.
+    app.edit_schedule(StateFlush, |schedule| {
+        schedule.set_build_settings(ScheduleBuildSettings {
+            auto_insert_apply_deferred: false,
+            ..default()
+        });
+    })
+    .add_systems(
+        StateFlush,
+        (|log: Res<Log>| assert_eq!(log.0, ["enter Title"]))
+            .in_set(ResolveStateSet::<Screen>::Enter)
+            .after(ResolveStateSet::<Screen>::AnyEnter),
+    );
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<Screen>>()
+        .enter(Screen::Title);
+    app.update();
+}
+
+#[test]
+fn local_observers_target_entity() {
+    let mut app = app();
+    let entity = app.world_mut().spawn_empty().id();
+    app.world_mut()
+        .commands()
+        .entity(entity)
+        .insert_state(NextStateBuffer::enabled(Screen::Title));
+    app.world_mut().flush();
+    app.world_mut()
+        .get_mut::<TriggerStateFlush<Screen>>(entity)
+        .unwrap()
+        .0 = true;
+    app.world_mut().entity_mut(entity).observe(
+        move |trigger: Trigger<OnStateEnter<Screen>>, mut log: ResMut<Log>| {
+            assert_eq!(trigger.target(), entity);
+            log.0.push("local enter".to_string());
+        },
+    );
+    app.update();
+
+    assert!(
+        app.world()
+            .resource::<Log>()
+            .0
+            .contains(&"local enter".to_string())
+    );
+}
@@ -0,0 +1,157 @@
+//! Tests for state flush guards.
+
+use bevy::{
+    ecs::{event::Events, system::RunSystemOnce as _},
+    prelude::*,
+};
+use pyri_state::{
+    next_state::TriggerStateFlush, prelude::*, schedule::resolve_state::StateFlushRejected,
+};
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+enum Screen {
+    Title,
+    Gameplay,
+}
+
+#[derive(State, Component, Clone, PartialEq, Eq, Debug)]
+#[state(local)]
+enum EnemyAi {
+    Idle,
+    Chase,
+}
+
+#[derive(Component)]
+struct Stunned;
+
+#[derive(Resource)]
+struct Allow(bool);
+
+fn allow(allow: Res<Allow>) -> bool {
+    allow.0
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .add_state::<Screen>()
+        .add_state::<EnemyAi>()
+        .insert_resource(Allow(false));
+    app
+}
+
+fn set_screen(app: &mut App, screen: Option<Screen>) {
+    app.world_mut()
+        .run_system_once(move |mut next: NextMut<Screen>| {
+            next.trigger().set(screen.clone());
+        })
+        .unwrap();
+    app.update();
+}
+
+fn set_ai(app: &mut App, entity: Entity, ai: EnemyAi) {
+    let mut entity = app.world_mut().entity_mut(entity);
+    entity
+        .get_mut::<NextStateBuffer<EnemyAi>>()
+        .unwrap()
+        .enter(ai);
+    entity.get_mut::<TriggerStateFlush<EnemyAi>>().unwrap().0 = true;
+}
+
+fn screen(app: &App) -> Option<&Screen> {
+    app.world().get_resource::<Screen>()
+}
+
+fn rejections<S: State + Clone>(app: &mut App) -> Vec<(Option<Entity>, Option<S>, Option<S>)> {
+    app.world_mut()
+        .resource_mut::<Events<StateFlushRejected<S>>>()
+        .drain()
+        .map(|event| (event.entity, event.old, event.new))
+        .collect()
+}
+
+#[test]
+fn guard_rejects_trans() {
+    let mut app = app();
+    app.add_systems(StateFlush, (Screen::Title, Screen::Gameplay).guard(allow));
+    set_screen(&mut app, Some(Screen::Title));
+
+    set_screen(&mut app, Some(Screen::Gameplay));
+    assert_eq!(screen(&app), Some(&Screen::Title));
+    assert_eq!(
+        rejections::<Screen>(&mut app),
+        [(None, Some(Screen::Title), Some(Screen::Gameplay))],
+    );
+
+    app.world_mut().resource_mut::<Allow>().0 = true;
+    set_screen(&mut app, Some(Screen::Gameplay));
+    assert_eq!(screen(&app), Some(&Screen::Gameplay));
+    assert!(rejections::<Screen>(&mut app).is_empty());
+}
+
+#[test]
+fn guard_rejects_enable_and_disable() {
+    let mut app = app();
+    app.add_systems(
+        StateFlush,
+        (
+            Screen::Title.guard_enable(allow),
+            Screen::ANY.guard_disable(allow),
+        ),
+    );
+
+    set_screen(&mut app, Some(Screen::Title));
+    assert_eq!(screen(&app), None);
+    assert_eq!(
+        rejections::<Screen>(&mut app),
+        [(None, None, Some(Screen::Title))],
+    );
+
+    app.world_mut().resource_mut::<Allow>().0 = true;
+    set_screen(&mut app, Some(Screen::Title));
+    app.world_mut().resource_mut::<Allow>().0 = false;
+    set_screen(&mut app, None);
+    assert_eq!(screen(&app), Some(&Screen::Title));
+    assert_eq!(
+        rejections::<Screen>(&mut app),
+        [(None, Some(Screen::Title), None)],
+    );
+}
+
+#[test]
+fn local_guard_rejects_per_entity() {
+    let mut app = app();
+    app.add_systems(
+        StateFlush,
+        (EnemyAi::Idle, EnemyAi::Chase).local_guard(
+            |In(entity): In<Entity>, stunned: Query<(), With<Stunned>>| !stunned.contains(entity),
+        ),
+    );
+    let stunned = app.world_mut().spawn(Stunned).id();
+    let free = app.world_mut().spawn_empty().id();
+    for entity in [stunned, free] {
+        app.world_mut()
+            .commands()
+            .entity(entity)
+            .insert_state(NextStateBuffer::<EnemyAi>::disabled());
+        app.world_mut().flush();
+        set_ai(&mut app, entity, EnemyAi::Idle);
+    }
+    app.update();
+
+    for entity in [stunned, free] {
+        set_ai(&mut app, entity, EnemyAi::Chase);
+    }
+    app.update();
+
+    assert_eq!(app.world().get::<EnemyAi>(stunned), Some(&EnemyAi::Idle));
+    assert_eq!(app.world().get::<EnemyAi>(free), Some(&EnemyAi::Chase));
+    assert_eq!(
+        rejections::<EnemyAi>(&mut app),
+        [(Some(stunned), Some(EnemyAi::Idle), Some(EnemyAi::Chase))],
+    );
+
+    // The rejected entity doesn't retry the flush on the next update.
+    app.update();
+    assert_eq!(app.world().get::<EnemyAi>(stunned), Some(&EnemyAi::Idle));
+}
@@ -0,0 +1,85 @@
+//! Tests for hierarchical state types.
+
+#![cfg(feature = "react")]
+
+mod common;
+
+use bevy::prelude::*;
+use pyri_state::prelude::*;
+
+use common::{Log, log, update};
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+#[state(hierarchical, react)]
+enum Screen {
+    Title,
+    Gameplay(Phase),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Phase {
+    Explore,
+    Combat,
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .add_state::<Screen>()
+        .init_resource::<Log>()
+        .add_systems(
+            StateFlush,
+            (
+                Screen::ANY.on_edge(log("any exit"), log("any enter")),
+                state!(Screen::Gameplay(_)).on_edge(log("gameplay exit"), log("gameplay enter")),
+                Screen::Gameplay(Phase::Combat).on_enter(log("combat enter")),
+            ),
+        );
+    enter(&mut app, Screen::Title);
+    app
+}
+
+fn enter(app: &mut App, screen: Screen) -> Vec<&'static str> {
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<Screen>>()
+        .enter(screen);
+    update(app)
+}
+
+#[test]
+fn hooks_skip_shared_levels() {
+    let mut app = app();
+
+    let log = enter(&mut app, Screen::Gameplay(Phase::Explore));
+    assert!(log.contains(&"gameplay enter"));
+
+    let log = enter(&mut app, Screen::Gameplay(Phase::Combat));
+    assert!(!log.contains(&"gameplay exit"));
+    assert!(!log.contains(&"gameplay enter"));
+    assert!(log.contains(&"combat enter"));
+}
+
+#[test]
+fn any_hooks_still_run() {
+    let mut app = app();
+
+    let log = enter(&mut app, Screen::Gameplay(Phase::Explore));
+    assert!(log.contains(&"any exit"));
+    assert!(log.contains(&"any enter"));
+
+    let log = enter(&mut app, Screen::Gameplay(Phase::Combat));
+    assert!(log.contains(&"any exit"));
+    assert!(log.contains(&"any enter"));
+}
+
+#[test]
+fn despawn_on_exit_state() {
+    let mut app = app();
+    let entity = app
+        .world_mut()
+        .spawn(DespawnOnExitState::<Screen>::default())
+        .id();
+
+    enter(&mut app, Screen::Gameplay(Phase::Explore));
+    assert!(app.world().get_entity(entity).is_err());
+}
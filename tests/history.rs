@@ -0,0 +1,135 @@
+//! Tests for state history navigation.
+
+#![cfg(feature = "history")]
+
+use bevy::{ecs::system::RunSystemOnce as _, prelude::*};
+use pyri_state::prelude::*;
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+#[state(history = 10)]
+enum Menu {
+    Main,
+    Settings,
+    Credits,
+}
+
+#[derive(Resource)]
+struct Allow(bool);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .add_state::<Menu>()
+        .insert_resource(Allow(true))
+        .add_systems(
+            StateFlush,
+            Menu::ANY_TO_ANY.guard(|allow: Res<Allow>| allow.0),
+        );
+    for menu in [Menu::Main, Menu::Settings, Menu::Credits] {
+        enter(&mut app, menu);
+    }
+    app
+}
+
+fn enter(app: &mut App, menu: Menu) {
+    app.world_mut()
+        .run_system_once(move |mut next: NextMut<Menu>| {
+            next.trigger().enter(menu.clone());
+        })
+        .unwrap();
+    app.update();
+}
+
+fn run<M>(app: &mut App, system: impl IntoSystem<(), (), M>) {
+    app.world_mut().run_system_once(system).unwrap();
+}
+
+fn menu(app: &App) -> Option<&Menu> {
+    app.world().get_resource::<Menu>()
+}
+
+fn history(app: &App) -> (Vec<Option<Menu>>, Vec<Option<Menu>>) {
+    let history = app.world().resource::<StateHistory<Menu>>();
+    (
+        history.past().map(|x| x.cloned()).collect(),
+        history.future().map(|x| x.cloned()).collect(),
+    )
+}
+
+#[test]
+fn back_and_forward() {
+    let mut app = app();
+    assert_eq!(
+        history(&app),
+        (vec![Some(Menu::Settings), Some(Menu::Main), None], vec![]),
+    );
+
+    run(&mut app, Menu::back);
+    app.update();
+    assert_eq!(menu(&app), Some(&Menu::Settings));
+    assert_eq!(
+        history(&app),
+        (vec![Some(Menu::Main), None], vec![Some(Menu::Credits)]),
+    );
+
+    run(&mut app, Menu::forward);
+    app.update();
+    assert_eq!(menu(&app), Some(&Menu::Credits));
+    assert_eq!(
+        history(&app),
+        (vec![Some(Menu::Settings), Some(Menu::Main), None], vec![]),
+    );
+}
+
+#[test]
+fn back_twice_before_flush() {
+    let mut app = app();
+    run(&mut app, Menu::back);
+    run(&mut app, Menu::back);
+    app.update();
+
+    assert_eq!(menu(&app), Some(&Menu::Settings));
+    assert_eq!(
+        history(&app),
+        (vec![Some(Menu::Main), None], vec![Some(Menu::Credits)]),
+    );
+}
+
+#[test]
+fn rejected_back_is_not_recorded() {
+    let mut app = app();
+    app.world_mut().resource_mut::<Allow>().0 = false;
+    run(&mut app, Menu::back);
+    app.update();
+    assert_eq!(menu(&app), Some(&Menu::Credits));
+    assert_eq!(
+        history(&app),
+        (vec![Some(Menu::Settings), Some(Menu::Main), None], vec![]),
+    );
+
+    // The next flush is recorded as a regular flush.
+    app.world_mut().resource_mut::<Allow>().0 = true;
+    enter(&mut app, Menu::Main);
+    assert_eq!(
+        history(&app),
+        (
+            vec![
+                Some(Menu::Credits),
+                Some(Menu::Settings),
+                Some(Menu::Main),
+                None
+            ],
+            vec![],
+        ),
+    );
+}
+
+#[test]
+fn refresh_is_not_recorded() {
+    let mut app = app();
+    enter(&mut app, Menu::Credits);
+    assert_eq!(
+        history(&app),
+        (vec![Some(Menu::Settings), Some(Menu::Main), None], vec![]),
+    );
+}
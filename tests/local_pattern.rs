@@ -0,0 +1,105 @@
+//! Tests for pattern-based local state hooks.
+
+mod common;
+
+use bevy::prelude::*;
+use pyri_state::{next_state::TriggerStateFlush, prelude::*};
+
+use common::update_log;
+
+#[derive(State, Component, Clone, PartialEq, Eq, Debug)]
+#[state(local)]
+enum EnemyAi {
+    Idle,
+    Chase,
+    Flee,
+}
+
+type Log = common::Log<(&'static str, Vec<Entity>)>;
+
+fn log(name: &'static str) -> impl Fn(In<Vec<Entity>>, ResMut<Log>) {
+    move |In(mut entities), mut log| {
+        entities.sort();
+        log.0.push((name, entities));
+    }
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .init_resource::<Log>()
+        .add_state::<EnemyAi>()
+        .add_systems(
+            StateFlush,
+            (
+                EnemyAi::Chase.on_local_exit(log("exit chase")),
+                EnemyAi::Chase.on_local_enter(log("enter chase")),
+                (EnemyAi::Chase, EnemyAi::Flee).on_local_trans(log("chase -> flee")),
+                state!(EnemyAi::Chase | EnemyAi::Flee).on_local_disable(log("disable")),
+            ),
+        );
+    app
+}
+
+fn set(app: &mut App, ai: &[(Entity, Option<EnemyAi>)]) -> Vec<(&'static str, Vec<Entity>)> {
+    for (entity, state) in ai {
+        let mut entity = app.world_mut().entity_mut(*entity);
+        entity
+            .get_mut::<NextStateBuffer<EnemyAi>>()
+            .unwrap()
+            .set(state.clone());
+        entity.get_mut::<TriggerStateFlush<EnemyAi>>().unwrap().0 = true;
+    }
+    update_log(app)
+}
+
+#[test]
+fn hooks_receive_matching_entities() {
+    let mut app = app();
+    let [a, b] = [(); 2].map(|_| app.world_mut().spawn_empty().id());
+    let mut commands = app.world_mut().commands();
+    for entity in [a, b] {
+        commands
+            .entity(entity)
+            .insert_state(NextStateBuffer::enabled(EnemyAi::Idle));
+    }
+    assert_eq!(set(&mut app, &[]), []);
+
+    assert_eq!(
+        set(
+            &mut app,
+            &[(a, Some(EnemyAi::Chase)), (b, Some(EnemyAi::Chase))]
+        ),
+        [("enter chase", vec![a, b])],
+    );
+    assert_eq!(
+        set(&mut app, &[(a, Some(EnemyAi::Flee))]),
+        [("chase -> flee", vec![a]), ("exit chase", vec![a])],
+    );
+    assert_eq!(
+        set(&mut app, &[(a, None), (b, None)]),
+        [("disable", vec![a, b]), ("exit chase", vec![b])],
+    );
+    assert_eq!(app.world().get::<EnemyAi>(a), None);
+}
+
+#[test]
+fn run_conditions_check_any_entity() {
+    let mut app = app();
+    app.add_systems(
+        Update,
+        (|mut log: ResMut<Log>| log.0.push(("chasing", vec![])))
+            .run_if(EnemyAi::Chase.will_local_update()),
+    );
+    let entity = app.world_mut().spawn_empty().id();
+    app.world_mut()
+        .commands()
+        .entity(entity)
+        .insert_state(NextStateBuffer::enabled(EnemyAi::Idle));
+
+    assert_eq!(set(&mut app, &[]), []);
+    assert_eq!(
+        set(&mut app, &[(entity, Some(EnemyAi::Chase))]),
+        [("chasing", vec![]), ("enter chase", vec![entity])],
+    );
+}
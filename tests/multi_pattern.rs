@@ -0,0 +1,75 @@
+//! Tests for patterns across multiple state types.
+
+mod common;
+
+use bevy::prelude::*;
+use pyri_state::prelude::*;
+
+use common::{Log, enter, log, update};
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+enum Screen {
+    Title,
+    Gameplay,
+}
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+enum Paused {
+    On,
+    Off,
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .init_resource::<Log>()
+        .add_state::<Screen>()
+        .add_state::<Paused>()
+        .add_systems(
+            StateFlush,
+            (
+                (Screen::Gameplay, Paused::Off).on_enter_all(log("enter")),
+                (Screen::Gameplay, Paused::Off).on_exit_all(log("exit")),
+            ),
+        )
+        .add_systems(
+            Update,
+            log("update").run_if((state!(Screen::Gameplay), Paused::Off).will_update()),
+        );
+    app
+}
+
+#[test]
+fn matches_when_every_state_matches() {
+    let mut app = app();
+    enter(&mut app, Screen::Title);
+    enter(&mut app, Paused::Off);
+    assert_eq!(update(&mut app), Vec::<&str>::new());
+
+    enter(&mut app, Screen::Gameplay);
+    assert_eq!(update(&mut app), ["enter", "update"]);
+    assert_eq!(update(&mut app), ["update"]);
+
+    enter(&mut app, Paused::On);
+    assert_eq!(update(&mut app), ["exit"]);
+
+    enter(&mut app, Paused::Off);
+    assert_eq!(update(&mut app), ["enter", "update"]);
+}
+
+#[test]
+fn enter_and_exit_once_when_both_states_change() {
+    let mut app = app();
+    enter(&mut app, Screen::Gameplay);
+    enter(&mut app, Paused::Off);
+    assert_eq!(update(&mut app), ["enter", "update"]);
+
+    // Both states stop matching in the same flush.
+    enter(&mut app, Screen::Title);
+    enter(&mut app, Paused::On);
+    assert_eq!(update(&mut app), ["exit"]);
+
+    // Only one state changes, and the other still doesn't match.
+    enter(&mut app, Screen::Gameplay);
+    assert_eq!(update(&mut app), Vec::<&str>::new());
+}
@@ -0,0 +1,76 @@
+//! Tests for combining patterns with `or`, `and`, `not`, and `except`.
+
+mod common;
+
+use bevy::prelude::*;
+use pyri_state::prelude::*;
+
+use common::{Log, log, update};
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+struct Level(usize);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .init_resource::<Log>()
+        .add_state::<Level>();
+    app
+}
+
+fn enter(app: &mut App, level: usize) -> Vec<&'static str> {
+    common::enter(app, Level(level));
+    update(app)
+}
+
+#[test]
+fn state_pattern_combinators() {
+    let mut app = app();
+    app.add_systems(
+        StateFlush,
+        (
+            Level(1).or(Level(2)).on_enter(log("or")),
+            Level::with(|x| x.0 > 1)
+                .and(Level::with(|x| x.0 < 4))
+                .on_enter(log("and")),
+            Level(0).not().on_exit(log("not")),
+            Level::with(|x| x.0 % 2 == 0)
+                .except(Level(0))
+                .on_enter(log("except")),
+        ),
+    )
+    .add_systems(Update, log("update").run_if(Level(0).not().will_update()));
+
+    assert_eq!(enter(&mut app, 0), Vec::<&str>::new());
+    assert_eq!(enter(&mut app, 1), ["or", "update"]);
+    assert_eq!(enter(&mut app, 2), ["and", "except", "not", "or", "update"]);
+    assert_eq!(enter(&mut app, 4), ["except", "not", "update"]);
+    assert_eq!(enter(&mut app, 0), ["not"]);
+}
+
+#[test]
+fn trans_pattern_combinators() {
+    let mut app = app();
+    app.add_systems(
+        StateFlush,
+        (
+            (Level(0), Level::ANY)
+                .or((Level::ANY, Level(0)))
+                .on_trans(log("or")),
+            Level::ANY_TO_ANY
+                .and(Level::when(|x, y| y.0 > x.0))
+                .on_trans(log("and")),
+            Level::when(|x, y| x == y).not().on_trans(log("not")),
+            Level::ANY_TO_ANY
+                .except(Level::when(|x, y| x == y))
+                .on_trans(log("except")),
+        ),
+    );
+
+    // Enabling the state isn't a transition.
+    assert_eq!(enter(&mut app, 0), Vec::<&str>::new());
+    assert_eq!(enter(&mut app, 2), ["and", "except", "not", "or"]);
+    assert_eq!(enter(&mut app, 2), Vec::<&str>::new());
+    assert_eq!(enter(&mut app, 1), ["except", "not"]);
+    assert_eq!(enter(&mut app, 0), ["except", "not", "or"]);
+}
@@ -0,0 +1,100 @@
+//! Tests for state flush reaction components.
+
+#![cfg(feature = "react")]
+
+use bevy::{ecs::entity_disabling::Disabled, prelude::*};
+use pyri_state::prelude::*;
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+#[state(react)]
+enum Screen {
+    Title,
+    Gameplay,
+    Pause,
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin).add_state::<Screen>();
+    app
+}
+
+fn enter(app: &mut App, screen: Screen) {
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<Screen>>()
+        .enter(screen);
+    app.update();
+}
+
+#[test]
+fn visible_in_state() {
+    let mut app = app();
+    let value = app
+        .world_mut()
+        .spawn((Visibility::Hidden, VisibleInState(Screen::Title)))
+        .id();
+    let pattern = app
+        .world_mut()
+        .spawn((
+            Visibility::Hidden,
+            VisibleInStatePattern::new(state!(Screen::Gameplay | Screen::Pause)),
+        ))
+        .id();
+    let visibility =
+        |app: &App| [value, pattern].map(|entity| *app.world().get::<Visibility>(entity).unwrap());
+
+    enter(&mut app, Screen::Title);
+    assert_eq!(
+        visibility(&app),
+        [Visibility::Inherited, Visibility::Hidden]
+    );
+
+    enter(&mut app, Screen::Gameplay);
+    assert_eq!(
+        visibility(&app),
+        [Visibility::Hidden, Visibility::Inherited]
+    );
+
+    enter(&mut app, Screen::Pause);
+    assert_eq!(
+        visibility(&app),
+        [Visibility::Hidden, Visibility::Inherited]
+    );
+
+    enter(&mut app, Screen::Title);
+    assert_eq!(
+        visibility(&app),
+        [Visibility::Inherited, Visibility::Hidden]
+    );
+}
+
+#[test]
+fn enabled_in_state() {
+    let mut app = app();
+    let value = app
+        .world_mut()
+        .spawn((Disabled, EnabledInState(Screen::Title)))
+        .id();
+    let pattern = app
+        .world_mut()
+        .spawn((
+            Disabled,
+            EnabledInStatePattern::new(state!(Screen::Gameplay | Screen::Pause)),
+        ))
+        .id();
+    let disabled = |app: &App| {
+        [value, pattern].map(|entity| app.world().entity(entity).contains::<Disabled>())
+    };
+
+    enter(&mut app, Screen::Title);
+    assert_eq!(disabled(&app), [false, true]);
+
+    enter(&mut app, Screen::Gameplay);
+    assert_eq!(disabled(&app), [true, false]);
+
+    enter(&mut app, Screen::Pause);
+    assert_eq!(disabled(&app), [true, false]);
+
+    enter(&mut app, Screen::Title);
+    assert_eq!(disabled(&app), [false, true]);
+}
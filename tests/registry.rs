@@ -0,0 +1,71 @@
+//! Tests for the state registry.
+
+#![cfg(feature = "debug")]
+
+use bevy::prelude::*;
+use pyri_state::{
+    debug::registry::StateRegistry, prelude::*, schedule::resolve_state::ResolveStatePlugin,
+    setup::RegisterState, state::State as StateTrait,
+};
+
+#[derive(State, Component, Clone, PartialEq, Eq, Debug)]
+#[state(local, detect_change)]
+enum Screen {
+    Title,
+    Gameplay,
+}
+
+// A state type with manual trait impls instead of the derive macro.
+#[derive(Resource, Clone, PartialEq, Eq, Debug)]
+struct Volume(u8);
+
+impl StateTrait for Volume {
+    type Next = NextStateBuffer<Self>;
+}
+
+impl RegisterState for Volume {
+    fn register_state(app: &mut App) {
+        app.add_plugins(ResolveStatePlugin::<Self>::default());
+    }
+}
+
+#[test]
+fn insert_state_registers() {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .insert_state(NextStateBuffer::enabled(Screen::Title));
+    app.update();
+    assert_eq!(app.world().get_resource::<Screen>(), Some(&Screen::Title));
+
+    let registry = app.world().resource::<StateRegistry>();
+    let info = registry.get::<Screen>().unwrap();
+    assert!(info.local);
+    assert!(info.plugins.contains(&"detect_change"));
+
+    // Inserting again replaces the next state without registering again.
+    app.insert_state(NextStateBuffer::enabled(Screen::Gameplay));
+    app.update();
+    assert_eq!(
+        app.world().get_resource::<Screen>(),
+        Some(&Screen::Gameplay)
+    );
+    assert_eq!(app.world().resource::<StateRegistry>().iter().count(), 1);
+}
+
+#[test]
+fn manual_impl_registers() {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .add_state::<Screen>()
+        .add_state::<Volume>();
+
+    let registry = app.world().resource::<StateRegistry>();
+    let names = registry
+        .iter()
+        .map(|info| info.type_name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["registry::Screen", "registry::Volume"]);
+    let info = registry.get::<Volume>().unwrap();
+    assert!(!info.local);
+    assert!(info.plugins.is_empty());
+}
@@ -0,0 +1,129 @@
+//! Tests for prioritized next state requests.
+
+#![cfg(feature = "requests")]
+
+use bevy::{ecs::system::RunSystemOnce as _, prelude::*};
+use pyri_state::prelude::*;
+
+#[derive(State, Component, Clone, PartialEq, Eq, Debug)]
+#[state(local, requests)]
+enum Screen {
+    Title,
+    Gameplay,
+    GameOver,
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin).add_state::<Screen>();
+    app
+}
+
+fn screen(app: &App) -> Option<&Screen> {
+    app.world().get_resource::<Screen>()
+}
+
+#[test]
+fn highest_priority_request_wins_and_triggers_flush() {
+    let mut app = app();
+    let mut next = app.world_mut().resource_mut::<NextStateRequests<Screen>>();
+    next.request_enter(0, Screen::Title);
+    next.request_enter(10, Screen::GameOver);
+    next.request_enter(10, Screen::Gameplay);
+    app.update();
+
+    assert_eq!(screen(&app), Some(&Screen::GameOver));
+    assert!(
+        app.world()
+            .resource::<NextStateRequests<Screen>>()
+            .requests()
+            .is_empty()
+    );
+
+    // Without requests, nothing is triggered.
+    app.update();
+    assert_eq!(screen(&app), Some(&Screen::GameOver));
+}
+
+#[test]
+fn commands_request_triggers_flush() {
+    let mut app = app();
+    app.world_mut()
+        .commands()
+        .state_request_enter(1, Screen::Title);
+    app.world_mut().flush();
+    app.update();
+    assert_eq!(screen(&app), Some(&Screen::Title));
+
+    app.world_mut()
+        .commands()
+        .state_request_disable::<Screen>(1);
+    app.world_mut().flush();
+    app.update();
+    assert_eq!(screen(&app), None);
+}
+
+#[test]
+fn next_mut_sets_next_state() {
+    let mut app = app();
+    app.world_mut()
+        .run_system_once(|mut next: NextMut<Screen>| {
+            next.trigger().enter(Screen::Title);
+        })
+        .unwrap();
+    app.update();
+    assert_eq!(screen(&app), Some(&Screen::Title));
+
+    app.world_mut().commands().set_state(Screen::Gameplay);
+    app.world_mut().flush();
+    app.update();
+    assert_eq!(screen(&app), Some(&Screen::Gameplay));
+}
+
+#[test]
+fn local_requests_trigger_flush() {
+    let mut app = app();
+    let entity = app.world_mut().spawn_empty().id();
+    let mut commands = app.world_mut().commands();
+    let mut entity_commands = commands.entity(entity);
+    entity_commands.insert_state(NextStateRequests::<Screen>::disabled());
+    entity_commands.state_request_enter(0, Screen::Title);
+    entity_commands.state_request_enter(5, Screen::Gameplay);
+    app.world_mut().flush();
+    app.update();
+
+    assert_eq!(app.world().get::<Screen>(entity), Some(&Screen::Gameplay));
+    assert_eq!(screen(&app), None);
+}
+
+#[derive(State, Component, Clone, PartialEq, Eq, Default, Debug)]
+#[state(local, requests)]
+enum Difficulty {
+    #[default]
+    Normal,
+    Hard,
+}
+
+#[test]
+fn init_state_with_requests() {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin).init_state::<Difficulty>();
+    app.update();
+    assert_eq!(
+        app.world().get_resource::<Difficulty>(),
+        Some(&Difficulty::Normal),
+    );
+
+    let entity = app.world_mut().spawn_empty().id();
+    let mut commands = app.world_mut().commands();
+    commands.entity(entity).init_state::<Difficulty>();
+    commands
+        .entity(entity)
+        .state_request_enter(0, Difficulty::Hard);
+    app.world_mut().flush();
+    app.update();
+    assert_eq!(
+        app.world().get::<Difficulty>(entity),
+        Some(&Difficulty::Hard)
+    );
+}
@@ -0,0 +1,126 @@
+//! Tests for next state sequences.
+
+#![cfg(feature = "sequence")]
+
+use bevy::{ecs::system::RunSystemOnce as _, prelude::*};
+use pyri_state::{next_state::TriggerStateFlush, prelude::*};
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+#[state(next(NextStateIndex<Self>))]
+enum Phase {
+    Upkeep,
+    Draw,
+    Main,
+    End,
+}
+
+fn sequence() -> NextStateSequence<Phase> {
+    NextStateSequence::new([Some(Phase::Upkeep), Some(Phase::Draw), Some(Phase::End)])
+        .with_label(1, "draw")
+        .with_label(2, "end")
+}
+
+#[test]
+fn labels_follow_entries() {
+    let mut sequence = sequence();
+
+    sequence.insert(2, Some(Phase::Main));
+    assert_eq!(sequence.len(), 4);
+    assert_eq!(sequence.find_label("draw"), Some(1));
+    assert_eq!(sequence.find_label("end"), Some(3));
+    assert_eq!(sequence.label(2), None);
+
+    assert_eq!(sequence.remove(0), Some(Phase::Upkeep));
+    assert_eq!(sequence.find_label("draw"), Some(0));
+    assert_eq!(sequence.find_label("end"), Some(2));
+    assert_eq!(sequence.get(2), Some(&Phase::End));
+
+    sequence.remove(0);
+    assert_eq!(sequence.find_label("draw"), None);
+    assert_eq!(sequence.labels().collect::<Vec<_>>(), [(1, "end")],);
+}
+
+#[test]
+fn seek_label_after_insert() {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .insert_resource(sequence())
+        .add_state::<Phase>();
+    app.world_mut()
+        .resource_mut::<NextStateSequence<Phase>>()
+        .insert(0, Some(Phase::Main));
+
+    app.world_mut()
+        .run_system_once(Phase::seek_label("end"))
+        .unwrap();
+    app.update();
+
+    assert_eq!(app.world().get_resource::<Phase>(), Some(&Phase::End));
+}
+
+#[derive(State, Component, Clone, PartialEq, Eq, Debug)]
+#[state(local, next(NextStateOwnedSequence<Self>))]
+enum Routine {
+    Sleep,
+    Work,
+    Eat,
+}
+
+#[test]
+fn owned_sequences_step_per_entity() {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin).add_state::<Routine>();
+    let [a, b] = [(); 2].map(|_| app.world_mut().spawn_empty().id());
+    let mut commands = app.world_mut().commands();
+    commands
+        .entity(a)
+        .insert_state(NextStateOwnedSequence::new(NextStateSequence::new([
+            Some(Routine::Sleep),
+            Some(Routine::Work),
+            Some(Routine::Eat),
+        ])));
+    commands.entity(b).insert_state(NextStateOwnedSequence::new(
+        NextStateSequence::new([Some(Routine::Work), None]).with_label(1, "off"),
+    ));
+    app.world_mut().flush();
+
+    let step = |app: &mut App, f: fn(&mut EntityCommands)| {
+        let mut commands = app.world_mut().commands();
+        for entity in [a, b] {
+            let mut entity = commands.entity(entity);
+            f(&mut entity);
+        }
+        app.world_mut().flush();
+        for entity in [a, b] {
+            app.world_mut()
+                .get_mut::<TriggerStateFlush<Routine>>(entity)
+                .unwrap()
+                .0 = true;
+        }
+        app.update();
+        [a, b].map(|entity| app.world().get::<Routine>(entity).cloned())
+    };
+
+    assert_eq!(
+        step(&mut app, |_| {}),
+        [Some(Routine::Sleep), Some(Routine::Work)],
+    );
+    assert_eq!(
+        step(&mut app, |entity| {
+            entity.state_next::<Routine>();
+        }),
+        [Some(Routine::Work), None],
+    );
+    assert_eq!(
+        step(&mut app, |entity| {
+            entity.state_wrapping_next::<Routine>();
+        }),
+        [Some(Routine::Eat), Some(Routine::Work)],
+    );
+    assert_eq!(
+        step(&mut app, |entity| {
+            entity.state_seek_label::<Routine>("off");
+        }),
+        [Some(Routine::Eat), None],
+    );
+}
@@ -0,0 +1,65 @@
+//! Tests for settle mode.
+
+use bevy::prelude::*;
+use pyri_state::{prelude::*, schedule::ApplyFlushSet};
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+enum Screen {
+    Gameplay,
+}
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+enum Music {
+    Intense,
+}
+
+// Trigger a flush after the flushes have been applied for this run of the `StateFlush` schedule.
+fn start_music(mut music: NextMut<Music>) {
+    music.trigger().enter(Music::Intense);
+}
+
+fn app(settle: bool) -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .add_state::<Screen>()
+        .add_state::<Music>();
+    if settle {
+        // States can be added before the settle plugin.
+        app.add_plugins(StateSettlePlugin::new(4));
+    }
+    app.add_systems(
+        StateFlush,
+        start_music
+            .run_if(Screen::Gameplay.will_update().and(Music::is_disabled))
+            .after(ApplyFlushSet),
+    );
+    app
+}
+
+fn enter_gameplay(app: &mut App) {
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<Screen>>()
+        .enter(Screen::Gameplay);
+    app.update();
+}
+
+#[test]
+fn without_settle_dependent_flush_waits_a_frame() {
+    let mut app = app(false);
+    enter_gameplay(&mut app);
+    assert_eq!(app.world().get_resource::<Music>(), None);
+
+    app.update();
+    assert_eq!(app.world().get_resource::<Music>(), Some(&Music::Intense));
+}
+
+#[test]
+fn settle_flushes_dependent_states_in_one_frame() {
+    let mut app = app(true);
+    enter_gameplay(&mut app);
+    assert_eq!(
+        app.world().get_resource::<Screen>(),
+        Some(&Screen::Gameplay)
+    );
+    assert_eq!(app.world().get_resource::<Music>(), Some(&Music::Intense));
+}
@@ -0,0 +1,140 @@
+//! Tests for state snapshots.
+
+#![cfg(feature = "snapshot")]
+
+use bevy::{ecs::system::RunSystemOnce as _, prelude::*};
+use pyri_state::{
+    extra::snapshot::{StateSnapshotDeserializer, StateSnapshotSerializer},
+    next_state::TriggerStateFlush,
+    prelude::*,
+    state::StateMut,
+};
+use serde::de::DeserializeSeed as _;
+
+#[derive(State, Reflect, Clone, PartialEq, Eq, Debug)]
+#[state(snapshot)]
+enum Screen {
+    Title,
+    Gameplay,
+}
+
+#[derive(State, Component, Reflect, Clone, PartialEq, Eq, Debug)]
+#[state(local, snapshot)]
+enum EnemyAi {
+    Idle,
+    Chase,
+}
+
+// Not opted in to `snapshot`, but registered for reflection.
+#[derive(State, Reflect, Clone, PartialEq, Eq, Debug)]
+#[reflect(Resource)]
+struct Level(usize);
+
+#[derive(Resource, Default)]
+struct Entered(Vec<Screen>);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .add_state::<Screen>()
+        .add_state::<Level>()
+        .add_state::<EnemyAi>()
+        .register_type::<Level>()
+        .register_type::<NextStateBuffer<Level>>()
+        .init_resource::<Entered>()
+        .add_systems(
+            StateFlush,
+            Screen::ANY.on_enter(|next: NextRef<Screen>, mut entered: ResMut<Entered>| {
+                entered.0.push(next.unwrap().clone());
+            }),
+        );
+    app
+}
+
+fn set<S: StateMut + Clone>(app: &mut App, state: S) {
+    app.world_mut()
+        .run_system_once(move |mut next: NextMut<S>| {
+            next.trigger().enter(state.clone());
+        })
+        .unwrap();
+    app.update();
+}
+
+// Serialize and deserialize a snapshot through RON.
+fn roundtrip(app: &mut App) -> StateSnapshot {
+    let snapshot = app.world_mut().snapshot_states();
+    let type_registry = app.world().resource::<AppTypeRegistry>().read();
+    let ron = ron::to_string(&StateSnapshotSerializer::new(&snapshot, &type_registry)).unwrap();
+    StateSnapshotDeserializer::new(&type_registry)
+        .deserialize(&mut ron::Deserializer::from_str(&ron).unwrap())
+        .unwrap()
+}
+
+#[test]
+fn restore_global_states() {
+    let mut app = app();
+    set(&mut app, Screen::Gameplay);
+    set(&mut app, Level(3));
+    let snapshot = roundtrip(&mut app);
+
+    set(&mut app, Screen::Title);
+    set(&mut app, Level(1));
+    app.world_mut().resource_mut::<Entered>().0.clear();
+    app.world_mut().restore_states(&snapshot);
+    app.update();
+
+    assert_eq!(
+        app.world().get_resource::<Screen>(),
+        Some(&Screen::Gameplay)
+    );
+    assert_eq!(app.world().get_resource::<Level>(), Some(&Level(3)));
+    assert_eq!(app.world().resource::<Entered>().0, [Screen::Gameplay]);
+}
+
+#[test]
+fn restore_local_states_by_id() {
+    let mut app = app();
+    let entity = app
+        .world_mut()
+        .spawn(StateSnapshotId("enemy".to_string()))
+        .id();
+    let mut commands = app.world_mut().commands();
+    let mut entity_commands = commands.entity(entity);
+    entity_commands.insert_state(NextStateBuffer::enabled(EnemyAi::Chase));
+    // Entities without a `StateSnapshotId` aren't included.
+    commands
+        .spawn_empty()
+        .insert_state(NextStateBuffer::enabled(EnemyAi::Chase));
+    app.world_mut().flush();
+    app.world_mut()
+        .get_mut::<TriggerStateFlush<EnemyAi>>(entity)
+        .unwrap()
+        .0 = true;
+    app.update();
+    let snapshot = roundtrip(&mut app);
+
+    // Load into a fresh entity with the same ID.
+    app.world_mut().despawn(entity);
+    let entity = app
+        .world_mut()
+        .spawn(StateSnapshotId("enemy".to_string()))
+        .id();
+    app.world_mut()
+        .commands()
+        .entity(entity)
+        .insert_state(NextStateBuffer::enabled(EnemyAi::Idle));
+    app.world_mut().flush();
+    app.update();
+    app.world_mut().restore_states(&snapshot);
+    app.update();
+
+    assert_eq!(app.world().get::<EnemyAi>(entity), Some(&EnemyAi::Chase));
+    let local = &snapshot
+        .0
+        .iter()
+        .find(|x| x.type_path.ends_with("EnemyAi"))
+        .unwrap()
+        .local;
+    assert_eq!(local.len(), 1);
+    assert_eq!(local[0].id, "enemy");
+}
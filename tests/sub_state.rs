@@ -0,0 +1,90 @@
+//! Tests for substates.
+
+use bevy::prelude::*;
+use pyri_state::prelude::*;
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+enum Screen {
+    Title,
+    Gameplay,
+    Credits,
+}
+
+#[derive(State, Clone, PartialEq, Eq, Default, Debug)]
+#[state(sub_of = Screen, when = Screen::Gameplay)]
+struct Level(usize);
+
+#[derive(State, Clone, PartialEq, Eq, Default, Debug)]
+#[state(sub_of = Screen, when = state!(Screen::Gameplay | Screen::Credits), remember)]
+struct Score(u32);
+
+#[derive(Resource, Default)]
+struct LevelsSpawned(usize);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin)
+        .init_resource::<LevelsSpawned>()
+        .add_state::<Screen>()
+        .add_state::<Level>()
+        .add_state::<Score>()
+        .add_systems(
+            StateFlush,
+            Level::ANY.on_enable(|mut spawned: ResMut<LevelsSpawned>| spawned.0 += 1),
+        );
+    app
+}
+
+fn enter(app: &mut App, screen: Screen) {
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<Screen>>()
+        .enter(screen);
+    app.update();
+}
+
+fn set<S: State<Next = NextStateBuffer<S>>>(app: &mut App, state: S) {
+    app.world_mut()
+        .resource_mut::<NextStateBuffer<S>>()
+        .enter(state);
+    app.update();
+}
+
+#[test]
+fn enabled_while_parent_matches() {
+    let mut app = app();
+    enter(&mut app, Screen::Title);
+    assert_eq!(app.world().get_resource::<Level>(), None);
+
+    // The substate is enabled and its enable hooks run in the same frame as the parent.
+    enter(&mut app, Screen::Gameplay);
+    assert_eq!(app.world().get_resource::<Level>(), Some(&Level(0)));
+    assert_eq!(app.world().resource::<LevelsSpawned>().0, 1);
+
+    set(&mut app, Level(3));
+    assert_eq!(app.world().get_resource::<Level>(), Some(&Level(3)));
+
+    enter(&mut app, Screen::Title);
+    assert_eq!(app.world().get_resource::<Level>(), None);
+
+    // Without `remember`, the substate is re-enabled with its default value.
+    enter(&mut app, Screen::Gameplay);
+    assert_eq!(app.world().get_resource::<Level>(), Some(&Level(0)));
+    assert_eq!(app.world().resource::<LevelsSpawned>().0, 2);
+}
+
+#[test]
+fn remember_restores_last_value() {
+    let mut app = app();
+    enter(&mut app, Screen::Gameplay);
+    set(&mut app, Score(100));
+
+    // Still matches the pattern, so the substate stays enabled.
+    enter(&mut app, Screen::Credits);
+    assert_eq!(app.world().get_resource::<Score>(), Some(&Score(100)));
+
+    enter(&mut app, Screen::Title);
+    assert_eq!(app.world().get_resource::<Score>(), None);
+
+    enter(&mut app, Screen::Gameplay);
+    assert_eq!(app.world().get_resource::<Score>(), Some(&Score(100)));
+}
@@ -0,0 +1,97 @@
+//! Tests for transition tables.
+
+use bevy::{
+    ecs::{event::Events, system::RunSystemOnce as _},
+    prelude::*,
+};
+use pyri_state::{prelude::*, schedule::resolve_state::StateFlushRejected};
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+#[state(transitions(Title => Gameplay, Gameplay => Title))]
+enum Screen {
+    Title,
+    Gameplay,
+    Credits,
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(StatePlugin).add_state::<Screen>();
+    app
+}
+
+fn set_screen(app: &mut App, screen: Option<Screen>) -> Option<&Screen> {
+    app.world_mut()
+        .run_system_once(move |mut next: NextMut<Screen>| {
+            next.trigger().set(screen.clone());
+        })
+        .unwrap();
+    app.update();
+    app.world().get_resource::<Screen>()
+}
+
+fn rejected(app: &mut App) -> usize {
+    app.world_mut()
+        .resource_mut::<Events<StateFlushRejected<Screen>>>()
+        .drain()
+        .count()
+}
+
+#[test]
+fn rejects_unlisted_transitions() {
+    let mut app = app();
+    assert_eq!(
+        set_screen(&mut app, Some(Screen::Title)),
+        Some(&Screen::Title)
+    );
+    assert_eq!(
+        set_screen(&mut app, Some(Screen::Credits)),
+        Some(&Screen::Title)
+    );
+    assert_eq!(rejected(&mut app), 1);
+    assert_eq!(
+        set_screen(&mut app, Some(Screen::Gameplay)),
+        Some(&Screen::Gameplay)
+    );
+    assert_eq!(rejected(&mut app), 0);
+}
+
+#[test]
+fn allows_enable_and_disable() {
+    let mut app = app();
+    assert_eq!(
+        set_screen(&mut app, Some(Screen::Credits)),
+        Some(&Screen::Credits)
+    );
+    assert_eq!(set_screen(&mut app, None), None);
+    assert_eq!(rejected(&mut app), 0);
+}
+
+#[test]
+fn allows_next_state_disabled_by_another_guard() {
+    let mut app = app();
+    app.add_systems(
+        StateFlush,
+        Screen::ANY_TO_ANY.guard(|mut state: FlushMut<Screen>| {
+            state.disable();
+            true
+        }),
+    );
+    set_screen(&mut app, Some(Screen::Title));
+    assert_eq!(set_screen(&mut app, Some(Screen::Gameplay)), None);
+    assert_eq!(rejected(&mut app), 0);
+}
+
+#[derive(State, Clone, PartialEq, Eq, Debug)]
+#[state(transitions(Self(1) => Self(2) | Self(3)))]
+struct Level(usize);
+
+#[test]
+fn struct_state_patterns() {
+    assert!(Level::allows(&Level(1), &Level(3)));
+    assert!(!Level::allows(&Level(2), &Level(1)));
+    assert_eq!(
+        Level::transitions(),
+        [("Self(1)", "Self(2)"), ("Self(1)", "Self(3)")],
+    );
+}